@@ -1,6 +1,17 @@
-use crate::{values::TypedArgumentKey, ArgumentKey};
+use crate::{
+    values::{Type, TypedArgumentKey},
+    ArgumentKey,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String};
+
+/// Errors of parsing templates, formatting them and loading their sources.
+///
+/// Some variants only exist with their features, e.g. `Io` with `std`,
+/// hence matches need a wildcard arm, which also covers variants added later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     ArgumentForTypeNotFound(TypedArgumentKey),
     ArgumentNotFound(ArgumentKey),
@@ -8,6 +19,51 @@ pub enum Error {
     UnexpectedArgumentValue,
     Fmt(core::fmt::Error),
     UnexpectedToken,
+    PartialNotFound(String),
+    PartialCycle(String),
+    TemplateNotFound(String),
+    InTemplate(String, Box<Error>),
+    NoMatch,
+    /// The type is denied by the [`TemplatePolicy`][crate::TemplatePolicy].
+    TypeNotAllowed(Type),
+    /// The fixed width exceeds the maximum of the [`TemplatePolicy`][crate::TemplatePolicy].
+    WidthTooLarge(u16),
+    /// The fixed precision exceeds the maximum of the [`TemplatePolicy`][crate::TemplatePolicy].
+    PrecisionTooLarge(u16),
+    /// The amount of placeholders exceeds the maximum of the [`TemplatePolicy`][crate::TemplatePolicy].
+    TooManyPlaceholders(usize),
+    /// The length of the template exceeds the maximum of the [`TemplatePolicy`][crate::TemplatePolicy].
+    TemplateTooLong(usize),
+    /// Dynamic width or precision is forbidden by the [`TemplatePolicy`][crate::TemplatePolicy].
+    DynamicWidthOrPrecision(ArgumentKey),
+    /// The output exceeds the [`OutputLimit`][crate::OutputLimit].
+    OutputLimitExceeded,
+    /// The custom type has no formatter in the [`FormatterRegistry`][crate::FormatterRegistry].
+    FormatterNotFound(String),
+    /// The filter of a placeholder is unknown, e.g. `{name|shout}`.
+    FilterNotFound(String),
+    /// The template nests deeper than the maximum depth, e.g. choices within the arms of choices.
+    NestingTooDeep(usize),
+    /// Reading the source of a template failed.
+    #[cfg(feature = "std")]
+    Io {
+        path: std::path::PathBuf,
+        kind: std::io::ErrorKind,
+        /// Description of the error, e.g. the message of the operating system.
+        message: String,
+    },
+    /// Invalid syntax in the line of a Fluent resource.
+    #[cfg(feature = "fluent")]
+    FluentSyntax(usize),
+    /// Invalid syntax in the line of a gettext `.po` catalog.
+    #[cfg(feature = "gettext")]
+    PoSyntax(usize),
+    /// Malformed gettext `.mo` catalog.
+    #[cfg(feature = "gettext")]
+    InvalidMo,
+    /// Invalid strftime pattern of a date and time placeholder.
+    #[cfg(feature = "chrono")]
+    InvalidDateTimeFormat(String),
 }
 
 #[cfg(feature = "error")]
@@ -32,6 +88,55 @@ impl core::fmt::Display for Error {
             Error::UnexpectedArgumentValue => write!(f, "Unexpeced argument value"),
             Error::Fmt(error) => core::fmt::Display::fmt(&error, f),
             Error::UnexpectedToken => write!(f, "Unexpected token encountered while parsing"),
+            Error::PartialNotFound(name) => write!(f, "Partial template '{}' not found", name),
+            Error::PartialCycle(name) => {
+                write!(f, "Partial template '{}' includes itself", name)
+            }
+            Error::TemplateNotFound(name) => write!(f, "Template '{}' not found", name),
+            Error::InTemplate(name, error) => write!(f, "In template '{}': {}", name, error),
+            Error::NoMatch => write!(f, "Input does not match the template"),
+            Error::TypeNotAllowed(ty) => write!(f, "Type '{:?}' is not allowed", ty),
+            Error::WidthTooLarge(width) => write!(f, "Width '{}' is too large", width),
+            Error::PrecisionTooLarge(precision) => {
+                write!(f, "Precision '{}' is too large", precision)
+            }
+            Error::TooManyPlaceholders(amount) => {
+                write!(f, "Too many placeholders '{}'", amount)
+            }
+            Error::TemplateTooLong(len) => write!(f, "Template length '{}' is too long", len),
+            Error::DynamicWidthOrPrecision(key) => {
+                write!(f, "Dynamic width or precision '{}' is not allowed", key)
+            }
+            Error::OutputLimitExceeded => write!(f, "Output exceeds the limit"),
+            Error::FormatterNotFound(name) => write!(f, "Formatter '{}' not found", name),
+            Error::FilterNotFound(name) => write!(f, "Filter '{}' not found", name),
+            Error::NestingTooDeep(depth) => write!(f, "Nesting exceeds the depth '{}'", depth),
+            #[cfg(feature = "std")]
+            Error::Io { path, message, .. } => {
+                write!(f, "IO error of '{}': {}", path.display(), message)
+            }
+            #[cfg(feature = "fluent")]
+            Error::FluentSyntax(line) => write!(f, "Invalid Fluent syntax in line {}", line),
+            #[cfg(feature = "gettext")]
+            Error::PoSyntax(line) => write!(f, "Invalid PO syntax in line {}", line),
+            #[cfg(feature = "gettext")]
+            Error::InvalidMo => write!(f, "Invalid MO catalog"),
+            #[cfg(feature = "chrono")]
+            Error::InvalidDateTimeFormat(pattern) => {
+                write!(f, "Invalid date time format '{}'", pattern)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error {
+    /// Keeps the path, kind and message of the error of reading the file.
+    pub(crate) fn io(path: &std::path::Path, error: &std::io::Error) -> Self {
+        Error::Io {
+            path: path.to_path_buf(),
+            kind: error.kind(),
+            message: error.to_string(),
         }
     }
 }
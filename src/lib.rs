@@ -11,10 +11,21 @@ mod materials;
 mod values;
 
 pub use error::Error;
+#[cfg(feature = "gettext")]
+pub use materials::Catalog;
+#[cfg(feature = "fluent")]
+pub use materials::FluentResource;
 pub use materials::{
-    Arguments, ArgumentsBuilder, DynFmt, Template, ToTemplate, UncheckedArgumentsBuilder,
+    Arguments, ArgumentsBuilder, Captures, CustomFormatter, DynFmt, FormatterRegistry,
+    Incompatibility, MacroTemplate, Partials, Template, TemplatePolicy, TemplateRegistry,
+    ToMacroTemplate, ToTemplate, UncheckedArgumentsBuilder,
 };
+#[cfg(feature = "parse_cache")]
+pub use materials::{ParseCache, ParseCacheStats};
 pub use values::{
-    Alignment, ArgumentKey, ArgumentTypeRequirements, ArgumentValue, Precision, Specifier,
-    ToArgumentKey, Type, TypedArgumentKey, Width, WidthOrPrecisionAmount,
+    Alignment, ArgumentKey, ArgumentTypeRequirements, ArgumentValue, AsDuration, Escape, Filter,
+    FormatDateTime, Grouping, Number, OutputLimit, Precision, Quantity, Segment, Specifier,
+    Spelling, ToArgumentKey, Type, TypedArgumentKey, Width, WidthOrPrecisionAmount,
 };
+#[cfg(feature = "locale")]
+pub use values::{Locale, PluralCategory};
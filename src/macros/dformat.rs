@@ -23,7 +23,9 @@
 /// | Width | `{:20}`, `{:width$}` |
 /// | Precision | `{:.5}`, `{:.precision$}`, `{:*}` |
 /// | Type | `?`, `x`, `X`, `o`, `b`, `e`, `E`, `p` |
+/// | Human-readable | `{size:>8.1B}`, `{size:iB}`, `{ratio:.1%}`, `{elapsed:t}`, `{elapsed:#t}` |
 /// | Argument keys | `{}`, `{0}`, `{arg}` |
+/// | Filters | `{name\|upper}`, `{title\|truncate(30, "…")}`, `{slug\|lower\|trim:>20}` |
 #[cfg(feature = "std")]
 #[macro_export]
 macro_rules! dformat {
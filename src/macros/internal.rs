@@ -5,8 +5,8 @@ macro_rules! __internal__dfmt {
         (|| -> Result<String, $crate::Error> {
             let t = &$template;
             let template = {
-                use $crate::ToTemplate;
-                t.to_template()
+                use $crate::ToMacroTemplate;
+                t.to_macro_template()
             }?;
             let mut arguments = template.arguments();
             $crate::__internal__dfmt_process_args!($checked, arguments, 0, $($args)*)?;
@@ -44,16 +44,7 @@ macro_rules! __internal__dfmt_process {
     ($checked:literal, $arguments:expr, $key:expr, $value:expr) => {{
         let requirements = $arguments.template.argument_type_requirements(&$key)?;
 
-        $crate::__internal__dfmt_black_magic!(
-            $checked,
-            requirements,
-            display,
-            $arguments,
-            $key,
-            $value,
-            Display,
-            core::fmt::Display
-        )?;
+        $crate::__internal__dfmt_number_magic!($checked, requirements, $arguments, $key, $value)?;
         $crate::__internal__dfmt_black_magic!(
             $checked,
             requirements,
@@ -144,6 +135,56 @@ macro_rules! __internal__dfmt_process {
             WidthOrPrecisionAmount,
             $crate::WidthOrPrecisionAmount
         )?;
+        $crate::__internal__dfmt_black_magic!(
+            $checked,
+            requirements,
+            bytes,
+            $arguments,
+            $key,
+            $value,
+            Bytes,
+            $crate::Quantity
+        )?;
+        $crate::__internal__dfmt_black_magic!(
+            $checked,
+            requirements,
+            binary_bytes,
+            $arguments,
+            $key,
+            $value,
+            BinaryBytes,
+            $crate::Quantity
+        )?;
+        $crate::__internal__dfmt_black_magic!(
+            $checked,
+            requirements,
+            percent,
+            $arguments,
+            $key,
+            $value,
+            Percent,
+            $crate::Quantity
+        )?;
+        $crate::__internal__dfmt_black_magic!(
+            $checked,
+            requirements,
+            duration,
+            $arguments,
+            $key,
+            $value,
+            Duration,
+            $crate::AsDuration
+        )?;
+        $crate::__internal__dfmt_black_magic!(
+            $checked,
+            requirements,
+            date_time,
+            $arguments,
+            $key,
+            $value,
+            DateTime,
+            $crate::FormatDateTime
+        )?;
 
         Ok(()) as Result<(), $crate::Error>
     }};
@@ -179,3 +220,41 @@ macro_rules! __internal__dfmt_black_magic {
         Ok(()) as Result<(), $crate::Error>
     }};
 }
+
+/// Passes numbers of displayed arguments as [`ArgumentValue::Number`][crate::ArgumentValue::Number],
+/// which takes precedence over the [`Display`][core::fmt::Display] of other values.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal__dfmt_number_magic {
+    ($checked:literal, $requirements:expr, $arguments:expr, $key:expr, $value:expr) => {{
+        if $requirements.display {
+            struct WrappedArg<'a, T>(&'a T);
+            trait BlackMagic<'a> {
+                fn to_argument_value(&self) -> Result<$crate::ArgumentValue<'a>, $crate::Error>;
+            }
+            impl<'a, T: $crate::Number> BlackMagic<'a> for &&WrappedArg<'a, T> {
+                fn to_argument_value(&self) -> Result<$crate::ArgumentValue<'a>, $crate::Error> {
+                    Ok($crate::ArgumentValue::Number(self.0))
+                }
+            }
+            impl<'a, T: core::fmt::Display> BlackMagic<'a> for &WrappedArg<'a, T> {
+                fn to_argument_value(&self) -> Result<$crate::ArgumentValue<'a>, $crate::Error> {
+                    Ok($crate::ArgumentValue::Display(self.0))
+                }
+            }
+            impl<'a, T> BlackMagic<'a> for WrappedArg<'a, T> {
+                fn to_argument_value(&self) -> Result<$crate::ArgumentValue<'a>, $crate::Error> {
+                    Err($crate::Error::UnexpectedArgumentValue)
+                }
+            }
+            let typed_value = (&&&WrappedArg(&$value)).to_argument_value()?;
+
+            if $checked {
+                $arguments.add_argument_value($key, typed_value)?;
+            } else {
+                $arguments.add_argument_value_unchecked($key, typed_value);
+            }
+        }
+        Ok(()) as Result<(), $crate::Error>
+    }};
+}
@@ -1,4 +1,7 @@
-use crate::{values::*, ArgumentKey, ArgumentValue, Error, Template, ToArgumentKey};
+use crate::{
+    values::*, ArgumentKey, ArgumentValue, Error, FormatterRegistry, Partials, Template,
+    ToArgumentKey,
+};
 use core::fmt::Write;
 
 #[cfg(not(feature = "std"))]
@@ -11,6 +14,11 @@ use alloc::{
 pub struct Arguments<'ct> {
     pub template: &'ct Template,
     pub(crate) argument_values: Vec<(ArgumentKey, ArgumentValue<'ct>)>,
+    pub(crate) escape: Escape,
+    pub(crate) output_limit: OutputLimit,
+    pub(crate) formatters: Option<&'ct FormatterRegistry>,
+    #[cfg(feature = "locale")]
+    pub(crate) locale: Locale,
 }
 
 impl<'ct> Arguments<'ct> {
@@ -19,46 +27,210 @@ impl<'ct> Arguments<'ct> {
         Self {
             template,
             argument_values: Vec::with_capacity(template.pieces.len()),
+            escape: Escape::None,
+            output_limit: OutputLimit::Unlimited,
+            formatters: None,
+            #[cfg(feature = "locale")]
+            locale: Locale::default(),
         }
     }
 
+    /// Sets the escaping of the formatted argument values. Defaults to [`Escape::None`].
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("echo {message}").unwrap();
+    /// let mut arguments = template.arguments();
+    /// arguments.set_escape(Escape::Shell);
+    /// arguments.add_argument_value("message", ArgumentValue::Display(&"it's $HOME")).unwrap();
+    /// assert_eq!(arguments.format().unwrap(), r"echo 'it'\''s $HOME'");
+    /// ```
+    pub fn set_escape(&mut self, escape: Escape) {
+        self.escape = escape;
+    }
+
+    /// Sets the maximum length of the output. Defaults to [`OutputLimit::Unlimited`].
+    /// The formatting stops writing once the limit is reached, even in the middle of a value.
+    pub fn set_output_limit(&mut self, output_limit: OutputLimit) {
+        self.output_limit = output_limit;
+    }
+
+    /// Sets the formatters of the custom types, e.g. `{amount:money}`.
+    /// See [`FormatterRegistry`] for an example.
+    pub fn set_formatters(&mut self, formatters: &'ct FormatterRegistry) {
+        self.formatters = Some(formatters);
+    }
+
+    /// Sets the locale, whose plural rules select the arms of `{count, plural, ...}` placeholders
+    /// and whose symbols are used for the numbers. Defaults to English.
+    ///
+    /// Numbers, i.e. [`ArgumentValue::Number`], get the decimal separator and digits of the locale,
+    /// while other displayed values like the string `"1.5"` are written unchanged.
+    /// The `,` grouping of templates, e.g. `{:,}`, becomes the grouping of the locale,
+    /// while other groupings keep their separator.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("{price:,.2} €").unwrap();
+    /// let mut arguments = template.arguments();
+    /// arguments.add_argument_value("price", ArgumentValue::Number(&1234.5)).unwrap();
+    /// arguments.set_locale(Locale::parse("de").unwrap());
+    /// assert_eq!(arguments.format().unwrap(), "1.234,50 €");
+    /// arguments.set_locale(Locale::parse("fr").unwrap());
+    /// assert_eq!(arguments.format().unwrap(), "1\u{202f}234,50 €");
+    /// ```
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// # #[cfg(feature = "message_format")] {
+    /// let template = Template::parse("{n, plural, one {# plik} few {# pliki} other {# plików}}").unwrap();
+    /// let mut arguments = template.arguments();
+    /// arguments.set_locale(Locale::parse("pl").unwrap());
+    /// arguments.add_argument_value("n", ArgumentValue::Number(&3)).unwrap();
+    /// assert_eq!(arguments.format().unwrap(), "3 pliki");
+    /// # }
+    /// ```
+    #[cfg(feature = "locale")]
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
     /// Clear the argument values again
     pub fn clear(&mut self) {
         self.argument_values.clear();
     }
 
+    fn get_argument_value(&self, key: &ArgumentKey, ty: &Type) -> Option<&ArgumentValue<'ct>> {
+        self.argument_values
+            .iter()
+            .find(|it| &it.0 == key && &it.1.to_type() == ty)
+            .map(|it| &it.1)
+    }
+
     fn find_argument_value(
         &self,
         key: &ArgumentKey,
         ty: &Type,
     ) -> Result<&ArgumentValue<'ct>, Error> {
-        self.argument_values
-            .iter()
-            .find(|it| &it.0 == key && &it.1.to_type() == ty)
-            .map(|it| &it.1)
+        self.get_argument_value(key, ty)
             .ok_or_else(|| Error::ArgumentForTypeNotFound(TypedArgumentKey::new(key.clone(), *ty)))
     }
 
     /// Attempt to format the template with the provided values.
     pub fn format(&self) -> Result<String, Error> {
-        let mut result = String::with_capacity(
-            self.template
-                .pieces
-                .iter()
-                .map(|piece| match piece {
-                    Piece::Literal(literal) => literal.len(),
-                    Piece::BracketOpen | Piece::BracketClose => 1,
-                    _ => 40,
-                })
-                .sum(),
+        // Fast path for templates without any arguments
+        if let [Piece::Literal(literal)] = self.template.pieces.as_slice() {
+            if self
+                .output_limit
+                .max_len()
+                .map_or(true, |max_len| literal.len() <= max_len)
+            {
+                return Ok(literal.clone());
+            }
+        }
+
+        self.format_pieces(None)
+    }
+
+    /// Attempt to format the template with the provided values,
+    /// while resolving `{>name}` placeholders against the partials.
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use dfmt::*;
+    ///
+    /// let mut partials = BTreeMap::new();
+    /// partials.insert("footer".to_string(), Template::parse("Regards, {sender}").unwrap());
+    ///
+    /// let template = Template::parse("Hello {name}!\n{>footer}").unwrap();
+    /// let mut arguments = template.arguments();
+    /// arguments.add_argument_value("name", ArgumentValue::Display(&"Tom")).unwrap();
+    /// arguments.add_argument_value("sender", ArgumentValue::Display(&"Ana")).unwrap();
+    /// assert_eq!(
+    ///     arguments.format_with_partials(&partials).unwrap(),
+    ///     "Hello Tom!\nRegards, Ana"
+    /// );
+    /// ```
+    pub fn format_with_partials(&self, partials: &dyn Partials) -> Result<String, Error> {
+        self.format_pieces(Some(partials))
+    }
+
+    fn format_pieces(&self, partials: Option<&dyn Partials>) -> Result<String, Error> {
+        let max_len = self.output_limit.max_len().unwrap_or(usize::MAX);
+        let mut result = String::with_capacity(self.template.capacity.min(max_len));
+        let written = self.write_pieces(
+            &mut LimitedOutput::new(&mut result, max_len),
+            &self.template.pieces,
+            partials,
+            &mut Vec::new(),
         );
 
-        for piece in &self.template.pieces {
+        match (written, &self.output_limit) {
+            (Err(Error::OutputLimitExceeded), OutputLimit::Truncate { max_len, ellipsis }) => {
+                truncate(&mut result, max_len.saturating_sub(ellipsis.len()));
+                result.push_str(ellipsis);
+                truncate(&mut result, *max_len);
+                Ok(result)
+            }
+            (written, _) => written.map(|_| result),
+        }
+    }
+
+    fn write_pieces<'a>(
+        &self,
+        result: &mut LimitedOutput<'_>,
+        pieces: &'a [Piece],
+        partials: Option<&'a dyn Partials>,
+        included: &mut Vec<&'a str>,
+    ) -> Result<(), Error> {
+        for piece in pieces {
             match piece {
-                Piece::Literal(literal) => result.push_str(literal),
-                Piece::BracketOpen => result.push('{'),
-                Piece::BracketClose => result.push('}'),
-                Piece::Argument { key, specifier } => {
+                Piece::Literal(literal) => {
+                    let written = result.write_str(literal);
+                    result.check(written)?;
+                }
+                Piece::BracketOpen => {
+                    let written = result.write_char('{');
+                    result.check(written)?;
+                }
+                Piece::BracketClose => {
+                    let written = result.write_char('}');
+                    result.check(written)?;
+                }
+                Piece::Partial(name) => {
+                    if included.contains(&name.as_str()) {
+                        return Err(Error::PartialCycle(name.clone()));
+                    }
+                    let partial = partials
+                        .and_then(|partials| partials.partial(name))
+                        .ok_or_else(|| Error::PartialNotFound(name.clone()))?;
+
+                    included.push(name);
+                    self.write_pieces(result, &partial.pieces, partials, included)?;
+                    included.pop();
+                }
+                #[cfg(feature = "message_format")]
+                Piece::Choice { key, kind, arms } => {
+                    let mut value = String::new();
+                    write!(value, "{}", self.find_argument_value(key, &Type::Display)?)
+                        .map_err(Error::Fmt)?;
+                    let arm = select_arm(*kind, arms, &value, &self.locale)?;
+                    self.write_pieces(result, arm, partials, included)?;
+                }
+                #[cfg(feature = "message_format")]
+                Piece::Count(key) => {
+                    let value = self.find_argument_value(key, &Type::Display)?;
+                    let number_format = self.number_format(None);
+                    self.write_escaped(result, false, |output| {
+                        let written = write_value(output, None, number_format, value, None, None);
+                        output.check(written)
+                    })?;
+                }
+                Piece::Argument {
+                    key,
+                    specifier,
+                    filters,
+                    ..
+                } => {
                     let ty = specifier.as_ref().map(|it| it.ty).unwrap_or(Type::Display);
                     let argument_value = self.find_argument_value(key, &ty)?;
 
@@ -85,19 +257,290 @@ impl<'ct> Arguments<'ct> {
                         None
                     };
 
-                    write_argument_value(
-                        &mut result,
+                    let raw = specifier.as_ref().is_some_and(|it| it.raw);
+                    if filters.is_empty() {
+                        self.write_escaped(result, raw, |output| {
+                            self.write_argument(
+                                output,
+                                specifier.as_ref(),
+                                argument_value,
+                                dynamic_width,
+                                dynamic_precision,
+                            )
+                        })?;
+                        continue;
+                    }
+
+                    let (filtered, exceeded) = self.write_filtered(
+                        result.remaining(),
+                        filters,
                         specifier.as_ref(),
                         argument_value,
-                        dynamic_width,
                         dynamic_precision,
-                    )
-                    .map_err(Error::Fmt)?;
+                    )?;
+                    self.write_escaped(result, raw, |output| {
+                        let written =
+                            write_padded(output, specifier.as_ref(), &filtered, dynamic_width);
+                        output.check(written)
+                    })?;
+                    if exceeded {
+                        result.exceeded = true;
+                        return Err(Error::OutputLimitExceeded);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The grouping, fraction digits and symbols of the numbers, if the std formatting can't write them.
+    fn number_format(&self, specifier: Option<&Specifier>) -> Option<NumberFormat> {
+        let mut number_format = NumberFormat::new(specifier.and_then(|it| it.grouping));
+        if let Some(
+            specifier @ Specifier {
+                ty: Type::Display, ..
+            },
+        ) = specifier
+        {
+            number_format.min_fraction_digits = specifier.min_fraction_digits;
+            number_format.min_integer_digits = specifier.min_integer_digits;
+        }
+        #[cfg(feature = "locale")]
+        let number_format = match specifier.map_or(Type::Display, |it| it.ty) {
+            Type::Display | Type::Debug => number_format.localized(&self.locale),
+            _ => number_format,
+        };
+        (number_format.grouping.is_some()
+            || number_format.min_fraction_digits.is_some()
+            || number_format.min_integer_digits.is_some()
+            || number_format.is_localized())
+        .then_some(number_format)
+    }
+
+    /// Writes the argument value by its specifier, without escaping it.
+    fn write_argument(
+        &self,
+        output: &mut LimitedOutput<'_>,
+        specifier: Option<&Specifier>,
+        argument_value: &ArgumentValue<'_>,
+        width: Option<u16>,
+        precision: Option<u16>,
+    ) -> Result<(), Error> {
+        if let Some(
+            specifier @ Specifier {
+                ty: Type::Custom(name),
+                ..
+            },
+        ) = specifier
+        {
+            let formatter = self
+                .formatters
+                .and_then(|formatters| formatters.get(name))
+                .ok_or_else(|| Error::FormatterNotFound(name.to_string()))?;
+            let ArgumentValue::Custom(_, value) = argument_value else {
+                return Err(Error::UnexpectedArgumentValue);
+            };
+
+            let specifier = specifier
+                .clone()
+                .width(Width::Fixed(width.unwrap_or(0)))
+                .precision(precision.map_or(Precision::Auto, Precision::Fixed));
+            let written = formatter(*value, &specifier, output);
+            return output.check(written);
+        }
+
+        let written = write_value(
+            output,
+            specifier,
+            self.number_format(specifier),
+            argument_value,
+            width,
+            precision,
+        );
+        output.check(written)
+    }
+
+    /// Writes the argument value without padding and applies the filters to the text.
+    ///
+    /// Values beyond the maximum length are cut before the filters,
+    /// hence the returned flag tells the output limit was exceeded.
+    fn write_filtered(
+        &self,
+        max_len: usize,
+        filters: &[Filter],
+        specifier: Option<&Specifier>,
+        argument_value: &ArgumentValue<'_>,
+        precision: Option<u16>,
+    ) -> Result<(String, bool), Error> {
+        let unpadded = specifier.map(|specifier| Specifier {
+            alignment: Alignment::Auto,
+            pad_zero: false,
+            width: Width::Fixed(0),
+            ..specifier.clone()
+        });
+
+        let mut text = String::new();
+        let mut scratch = LimitedOutput::new(&mut text, max_len);
+        match self.write_argument(
+            &mut scratch,
+            unpadded.as_ref(),
+            argument_value,
+            unpadded.as_ref().map(|_| 0),
+            precision,
+        ) {
+            Ok(()) | Err(Error::OutputLimitExceeded) => {}
+            Err(error) => return Err(error),
+        }
+        let exceeded = scratch.exceeded;
+
+        let filtered = filters
+            .iter()
+            .fold(text, |text, filter| filter.apply(&text));
+        Ok((filtered, exceeded))
+    }
+
+    /// Writes the value through a scratch buffer, unless it is written as is.
+    fn write_escaped(
+        &self,
+        result: &mut LimitedOutput<'_>,
+        raw: bool,
+        write: impl FnOnce(&mut LimitedOutput<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        if raw || self.escape == Escape::None {
+            return write(result);
+        }
+
+        // Escaping never shortens the value, hence the scratch buffer shares the limit
+        let mut value = String::new();
+        let mut scratch = LimitedOutput::new(&mut value, result.remaining());
+        let written = write(&mut scratch);
+        let exceeded = scratch.exceeded;
+        if !exceeded {
+            written?;
+        }
+
+        let escaped = self.escape.write(result, &value);
+        result.check(escaped)?;
+        if exceeded {
+            result.exceeded = true;
+            return Err(Error::OutputLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Formats every argument piece whose values are present into a literal
+    /// and returns the template of the remaining pieces.
+    pub(crate) fn bind(&self) -> Result<Template, Error> {
+        let mut pieces = Vec::with_capacity(self.template.pieces.len());
+        let mut spans = Vec::with_capacity(self.template.spans.len());
+
+        for (piece, span) in self.template.pieces.iter().zip(&self.template.spans) {
+            let Piece::Argument {
+                key,
+                specifier,
+                filters,
+                ..
+            } = piece
+            else {
+                pieces.push(piece.clone());
+                spans.push(span.clone());
+                continue;
+            };
+
+            let mut bound_specifier = specifier.clone();
+            if let Some(specifier) = &mut bound_specifier {
+                if let Width::Dynamic(width_key) = &specifier.width {
+                    if let Some(amount) = self
+                        .get_argument_value(width_key, &Type::WidthOrPrecisionAmount)
+                        .and_then(ArgumentValue::to_u16)
+                    {
+                        specifier.width = Width::Fixed(amount);
+                    }
+                }
+                if let Precision::Dynamic(precision_key) = &specifier.precision {
+                    if let Some(amount) = self
+                        .get_argument_value(precision_key, &Type::WidthOrPrecisionAmount)
+                        .and_then(ArgumentValue::to_u16)
+                    {
+                        specifier.precision = Precision::Fixed(amount);
+                    }
+                }
+            }
+
+            let ty = bound_specifier
+                .as_ref()
+                .map(|it| it.ty)
+                .unwrap_or(Type::Display);
+            let is_resolved = bound_specifier.as_ref().map_or(true, |it| {
+                !matches!(it.width, Width::Dynamic(_))
+                    && !matches!(it.precision, Precision::Dynamic(_))
+            });
+
+            match self.get_argument_value(key, &ty) {
+                // Custom types require the formatters, which are only known when formatting
+                Some(argument_value) if is_resolved && !matches!(ty, Type::Custom(_)) => {
+                    let mut literal = String::new();
+                    let (width, precision) = match &bound_specifier {
+                        Some(Specifier {
+                            width: Width::Fixed(width),
+                            precision,
+                            ..
+                        }) => (
+                            Some(*width),
+                            match precision {
+                                Precision::Fixed(precision) => Some(*precision),
+                                _ => None,
+                            },
+                        ),
+                        _ => (None, None),
+                    };
+                    let mut output = LimitedOutput::new(&mut literal, usize::MAX);
+                    if filters.is_empty() {
+                        write_value(
+                            &mut output,
+                            bound_specifier.as_ref(),
+                            self.number_format(bound_specifier.as_ref()),
+                            argument_value,
+                            width,
+                            precision,
+                        )
+                        .map_err(Error::Fmt)?;
+                    } else {
+                        let (filtered, _) = self.write_filtered(
+                            usize::MAX,
+                            filters,
+                            bound_specifier.as_ref(),
+                            argument_value,
+                            precision,
+                        )?;
+                        write_padded(&mut output, bound_specifier.as_ref(), &filtered, width)
+                            .map_err(Error::Fmt)?;
+                    }
+                    pieces.push(Piece::Literal(literal));
+                    spans.push(None);
+                }
+                _ => {
+                    // The positional counter no longer matches after other pieces are bound,
+                    // hence the remaining keys are made explicit.
+                    let bound_piece = Piece::Argument {
+                        key: key.clone(),
+                        specifier: bound_specifier,
+                        implicit_key: false,
+                        implicit_precision: false,
+                        filters: filters.clone(),
+                    };
+                    spans.push(if &bound_piece == piece {
+                        span.clone()
+                    } else {
+                        None
+                    });
+                    pieces.push(bound_piece);
                 }
             }
         }
 
-        Ok(result)
+        Ok(Template::from_pieces(pieces, spans, self.template.source.clone()).optimize())
     }
 
     // Builder
@@ -155,9 +598,362 @@ impl<'ct> core::fmt::Debug for Arguments<'ct> {
     }
 }
 
+fn write_value(
+    output: &mut LimitedOutput<'_>,
+    specifier: Option<&Specifier>,
+    number_format: Option<NumberFormat>,
+    value: &ArgumentValue<'_>,
+    width: Option<u16>,
+    precision: Option<u16>,
+) -> core::fmt::Result {
+    // The pattern is only known by the specifier, hence the date or time is formatted by a closure
+    if let (
+        ArgumentValue::DateTime(date_time),
+        Some(Specifier {
+            date_time_format: Some(pattern),
+            ..
+        }),
+    ) = (value, specifier)
+    {
+        let format = |f: &mut core::fmt::Formatter<'_>| date_time.fmt_date_time(f, pattern);
+        return write_argument_value(
+            output,
+            specifier,
+            &ArgumentValue::Lazy(&format),
+            width,
+            precision,
+        );
+    }
+
+    // Only numbers are grouped and localized, other displayed values are written unchanged
+    match (number_format, value) {
+        (Some(number_format), ArgumentValue::Number(_)) => {
+            write_number_argument_value(output, specifier, number_format, value, width, precision)
+        }
+        _ => write_argument_value(output, specifier, value, width, precision),
+    }
+}
+
+/// Pads the filtered text like a string, which is left aligned by default and ignores the zero padding.
+fn write_padded(
+    output: &mut LimitedOutput<'_>,
+    specifier: Option<&Specifier>,
+    text: &str,
+    width: Option<u16>,
+) -> core::fmt::Result {
+    let Some(specifier) = specifier else {
+        return output.write_str(text);
+    };
+    let padding = usize::from(width.unwrap_or(0)).saturating_sub(text.chars().count());
+    let (before, after) = match specifier.alignment {
+        Alignment::Left | Alignment::Auto => (0, padding),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+        Alignment::Right => (padding, 0),
+    };
+
+    for _ in 0..before {
+        output.write_char(specifier.fill_character)?;
+    }
+    output.write_str(text)?;
+    for _ in 0..after {
+        output.write_char(specifier.fill_character)?;
+    }
+    Ok(())
+}
+
+/// Grouping, fraction digits and symbols of the numbers,
+/// which the std formatting writes with `.`, a fixed precision and ASCII digits.
+#[derive(Clone, Copy)]
+struct NumberFormat {
+    grouping: Option<Grouping>,
+    min_fraction_digits: Option<u16>,
+    min_integer_digits: Option<u16>,
+    decimal_separator: char,
+    zero_digit: char,
+}
+
+impl NumberFormat {
+    fn new(grouping: Option<Grouping>) -> Self {
+        Self {
+            grouping,
+            min_fraction_digits: None,
+            min_integer_digits: None,
+            decimal_separator: '.',
+            zero_digit: '0',
+        }
+    }
+
+    /// Takes the symbols of the locale, whose grouping replaces the `,` grouping of templates.
+    #[cfg(feature = "locale")]
+    fn localized(self, locale: &Locale) -> Self {
+        Self {
+            grouping: match self.grouping {
+                Some(grouping) if grouping == Grouping::new(',') => Some(locale.grouping()),
+                grouping => grouping,
+            },
+            decimal_separator: locale.decimal_separator(),
+            zero_digit: locale.zero_digit(),
+            ..self
+        }
+    }
+
+    fn is_localized(&self) -> bool {
+        self.decimal_separator != '.' || self.zero_digit != '0'
+    }
+
+    fn push_digits(&self, output: &mut String, digits: &str) {
+        output.extend(digits.bytes().map(|digit| {
+            char::from_u32(self.zero_digit as u32 + (digit - b'0') as u32).unwrap_or('?')
+        }));
+    }
+
+    fn grouped_len(&self, digits_len: usize) -> usize {
+        self.grouping
+            .map_or(digits_len, |grouping| grouping.grouped_len(digits_len))
+    }
+
+    fn write_digits(&self, output: &mut impl Write, digits: &str) -> core::fmt::Result {
+        match self.grouping {
+            Some(grouping) => grouping.write(output, digits),
+            None => output.write_str(digits),
+        }
+    }
+}
+
+/// Writes the value without padding once, then groups the digits of its integer part,
+/// trims the trailing zeros of its fraction, replaces the symbols of decimal numbers
+/// and pads it to the width, because the std formatting has neither grouping nor other symbols.
+fn write_number_argument_value(
+    output: &mut LimitedOutput<'_>,
+    specifier: Option<&Specifier>,
+    number_format: NumberFormat,
+    value: &ArgumentValue<'_>,
+    width: Option<u16>,
+    precision: Option<u16>,
+) -> core::fmt::Result {
+    let default_specifier = Specifier::default();
+    let padded_specifier = specifier.unwrap_or(&default_specifier);
+    let unpadded_specifier = Specifier {
+        alignment: Alignment::Auto,
+        pad_zero: false,
+        width: Width::Fixed(0),
+        grouping: None,
+        ..padded_specifier.clone()
+    };
+    let mut unpadded = String::new();
+    let mut scratch = LimitedOutput::new(&mut unpadded, output.remaining());
+    let written = write_argument_value(
+        &mut scratch,
+        Some(&unpadded_specifier),
+        value,
+        Some(0),
+        precision,
+    );
+    if written.is_err() {
+        if scratch.exceeded {
+            output.write_str(&unpadded)?;
+            output.exceeded = true;
+        }
+        return written;
+    }
+
+    let sign_len = usize::from(unpadded.starts_with(['+', '-']));
+    let digits_len = unpadded[sign_len..]
+        .bytes()
+        .take_while(u8::is_ascii_digit)
+        .count();
+    let (sign, rest) = unpadded.split_at(sign_len);
+    let (digits, tail) = rest.split_at(digits_len);
+
+    // Only plain decimal numbers are grouped and localized, e.g. not `1e-7`, `v1.2` or `2024 build`
+    let fraction = tail
+        .strip_prefix('.')
+        .filter(|fraction| !fraction.is_empty() && fraction.bytes().all(|it| it.is_ascii_digit()));
+    let is_number = digits_len > 0 && (tail.is_empty() || fraction.is_some());
+    let width = width.unwrap_or(0) as usize;
+    if !is_number {
+        // Infinity and NaN are written unchanged, but padded like the std formatting does, e.g. `-0inf`
+        let padding = width.saturating_sub(unpadded.chars().count());
+        if padded_specifier.pad_zero {
+            output.write_str(sign)?;
+            for _ in 0..padding {
+                output.write_char('0')?;
+            }
+            return output.write_str(rest);
+        }
+        return write_aligned(output, padded_specifier, padding, |output| {
+            output.write_str(&unpadded)
+        });
+    }
+    let fraction = match (fraction, number_format.min_fraction_digits) {
+        (Some(fraction), Some(min_fraction_digits)) => {
+            let trimmed_len = fraction.trim_end_matches('0').len();
+            Some(&fraction[..trimmed_len.max(min_fraction_digits.into())])
+                .filter(|fraction| !fraction.is_empty())
+        }
+        (fraction, _) => fraction,
+    };
+    let integer_zeros =
+        usize::from(number_format.min_integer_digits.unwrap_or(0)).saturating_sub(digits_len);
+    let digits_len = digits_len + integer_zeros;
+    let mut localized_digits: String = core::iter::repeat(number_format.zero_digit)
+        .take(integer_zeros)
+        .collect();
+    number_format.push_digits(&mut localized_digits, digits);
+    let mut localized_tail = String::new();
+    if let Some(fraction) = fraction {
+        localized_tail.push(number_format.decimal_separator);
+        number_format.push_digits(&mut localized_tail, fraction);
+    }
+    let (digits, tail) = (localized_digits, localized_tail);
+
+    let unpadded_len = |digits_len: usize, tail_len: usize| {
+        sign.len() + number_format.grouped_len(digits_len) + tail_len
+    };
+
+    if padded_specifier.pad_zero {
+        // The zeros are grouped as well, e.g. `00,001,234`
+        let mut zeros = 0;
+        while unpadded_len(digits_len + zeros, tail.chars().count()) < width {
+            zeros += 1;
+        }
+        let mut padded_digits: String = core::iter::repeat(number_format.zero_digit)
+            .take(zeros)
+            .collect();
+        padded_digits.push_str(&digits);
+
+        output.write_str(sign)?;
+        number_format.write_digits(output, &padded_digits)?;
+        return output.write_str(&tail);
+    }
+
+    let padding = width.saturating_sub(unpadded_len(digits_len, tail.chars().count()));
+    write_aligned(output, padded_specifier, padding, |output| {
+        output.write_str(sign)?;
+        number_format.write_digits(output, &digits)?;
+        output.write_str(&tail)
+    })
+}
+
+/// Surrounds the number by the padding, which is right aligned by default.
+fn write_aligned(
+    output: &mut LimitedOutput<'_>,
+    specifier: &Specifier,
+    padding: usize,
+    write: impl FnOnce(&mut LimitedOutput<'_>) -> core::fmt::Result,
+) -> core::fmt::Result {
+    let (before, after) = match specifier.alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+        Alignment::Right | Alignment::Auto => (padding, 0),
+    };
+    for _ in 0..before {
+        output.write_char(specifier.fill_character)?;
+    }
+    write(output)?;
+    for _ in 0..after {
+        output.write_char(specifier.fill_character)?;
+    }
+    Ok(())
+}
+
+/// Output of the formatting, which stops writing once its maximum length in bytes is reached.
+struct LimitedOutput<'s> {
+    string: &'s mut String,
+    max_len: usize,
+    exceeded: bool,
+}
+
+impl<'s> LimitedOutput<'s> {
+    fn new(string: &'s mut String, max_len: usize) -> Self {
+        Self {
+            string,
+            max_len,
+            exceeded: false,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.max_len.saturating_sub(self.string.len())
+    }
+
+    /// Maps the result of writing, while telling the exceeded limit apart from formatting errors.
+    fn check(&self, written: core::fmt::Result) -> Result<(), Error> {
+        written.map_err(|error| {
+            if self.exceeded {
+                Error::OutputLimitExceeded
+            } else {
+                Error::Fmt(error)
+            }
+        })
+    }
+}
+
+impl Write for LimitedOutput<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let remaining = self.remaining();
+        if s.len() <= remaining {
+            self.string.push_str(s);
+            return Ok(());
+        }
+
+        self.string
+            .push_str(&s[..floor_char_boundary(s, remaining)]);
+        self.exceeded = true;
+        Err(core::fmt::Error)
+    }
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn truncate(string: &mut String, max_len: usize) {
+    if string.len() > max_len {
+        string.truncate(floor_char_boundary(string, max_len));
+    }
+}
+
+/// Selects the arm by an exact `=N` match or the plural category for plurals,
+/// and by the text for selects, falling back to the `other` arm.
+#[cfg(feature = "message_format")]
+fn select_arm<'p>(
+    kind: ChoiceKind,
+    arms: &'p [(String, Vec<Piece>)],
+    value: &str,
+    locale: &Locale,
+) -> Result<&'p [Piece], Error> {
+    let selector = match kind {
+        ChoiceKind::Select => value,
+        ChoiceKind::Plural => {
+            let exact = arms.iter().find(|(selector, _)| {
+                selector
+                    .strip_prefix('=')
+                    .is_some_and(|number| locale::is_same_number(number, value))
+            });
+            if let Some((_, arm)) = exact {
+                return Ok(arm);
+            }
+            locale
+                .plural_category(value)
+                .ok_or(Error::UnexpectedArgumentValue)?
+                .as_str()
+        }
+    };
+
+    arms.iter()
+        .find(|(other, _)| other == selector)
+        .or_else(|| arms.iter().find(|(other, _)| other == "other"))
+        .map(|(_, arm)| arm.as_slice())
+        .ok_or(Error::UnexpectedArgumentValue)
+}
+
 #[cfg(feature = "nightly_formatting_options")]
 fn write_argument_value(
-    output: &mut String,
+    output: &mut LimitedOutput<'_>,
     specifier: Option<&Specifier>,
     value: &ArgumentValue<'_>,
     width: Option<u16>,
@@ -177,417 +973,444 @@ fn write_argument_value(
 
 #[cfg(not(feature = "nightly_formatting_options"))]
 fn write_argument_value(
-    output: &mut String,
+    output: &mut LimitedOutput<'_>,
     specifier: Option<&Specifier>,
     value: &ArgumentValue<'_>,
     width: Option<u16>,
     precision: Option<u16>,
 ) -> core::fmt::Result {
-    if let Some(specifier) = specifier {
-        let result = match (
-            specifier.alignment,
-            specifier.sign,
-            specifier.alternate_form,
-            specifier.pad_zero,
-            &specifier.precision,
-        ) {
-            (Alignment::Auto, true, true, true, Precision::Auto) => {
-                write!(output, "{:+#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, true, true, true, _) => {
-                write!(
-                    output,
-                    "{:+#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, true, true, false, Precision::Auto) => {
-                write!(output, "{:+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, true, true, false, _) => {
-                write!(
-                    output,
-                    "{:+#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, true, false, true, Precision::Auto) => {
-                write!(output, "{:+0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, true, false, true, _) => {
-                write!(
-                    output,
-                    "{:+0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, true, false, false, Precision::Auto) => {
-                write!(output, "{:+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, true, false, false, _) => {
-                write!(
-                    output,
-                    "{:+w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, false, true, true, Precision::Auto) => {
-                write!(output, "{:#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, false, true, true, _) => {
-                write!(
-                    output,
-                    "{:#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, false, true, false, Precision::Auto) => {
-                write!(output, "{:#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, false, true, false, _) => {
-                write!(
-                    output,
-                    "{:#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, false, false, true, Precision::Auto) => {
-                write!(output, "{:0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, false, false, true, _) => {
-                write!(
-                    output,
-                    "{:0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Auto, false, false, false, Precision::Auto) => {
-                write!(output, "{:w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Auto, false, false, false, _) => {
-                write!(
-                    output,
-                    "{:w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
+    let Some(specifier) = specifier else {
+        return write!(output, "{}", value);
+    };
+    if specifier.alignment == Alignment::Auto {
+        return write_specified_argument_value(output, specifier, value, width, precision);
+    }
 
-            (Alignment::Left, true, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ<+#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, true, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<+#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, true, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ<+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, true, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<+#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, true, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ<+0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, true, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<+0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, true, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ<+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, true, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<+w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, false, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ<#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, false, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, false, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ<#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, false, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, false, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ<0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, false, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Left, false, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ<w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Left, false, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ<w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
+    // The fill character can't be passed to `write!`, hence the padding is written as ꙮ
+    // into a scratch buffer first, whose limit leaves room for the wider placeholder.
+    let mut padded = String::new();
+    let max_len = output
+        .remaining()
+        .saturating_add(width.unwrap_or(0) as usize * 'ꙮ'.len_utf8());
+    let mut scratch = LimitedOutput::new(&mut padded, max_len);
+    let written = write_specified_argument_value(&mut scratch, specifier, value, width, precision);
+    if written.is_err() && !scratch.exceeded {
+        return written;
+    }
+    let exceeded = scratch.exceeded;
 
-            (Alignment::Center, true, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ^+#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, true, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^+#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, true, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ^+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, true, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^+#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, true, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ^+0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, true, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^+0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, true, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ^+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, true, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^+w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, false, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ^#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, false, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, false, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ^#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, false, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, false, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ^0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, false, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Center, false, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ^w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Center, false, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ^w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
+    if width.is_some_and(|width| width > 0) && padded.contains('ꙮ') {
+        padded = padded.replace('ꙮ', &specifier.fill_character.to_string());
+    }
+    output.write_str(&padded)?;
+    if exceeded {
+        output.exceeded = true;
+        return Err(core::fmt::Error);
+    }
+    Ok(())
+}
 
-            (Alignment::Right, true, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ>+#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, true, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>+#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, true, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ>+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, true, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>+#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, true, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ>+0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, true, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>+0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, true, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ>+#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, true, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>+w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, false, true, true, Precision::Auto) => {
-                write!(output, "{:ꙮ>#0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, false, true, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>#0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, false, true, false, Precision::Auto) => {
-                write!(output, "{:ꙮ>#w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, false, true, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>#w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, false, false, true, Precision::Auto) => {
-                write!(output, "{:ꙮ>0w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, false, false, true, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>0w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-            (Alignment::Right, false, false, false, Precision::Auto) => {
-                write!(output, "{:ꙮ>w$}", value, w = width.unwrap() as usize)
-            }
-            (Alignment::Right, false, false, false, _) => {
-                write!(
-                    output,
-                    "{:ꙮ>w$.p$}",
-                    value,
-                    w = width.unwrap() as usize,
-                    p = precision.unwrap() as usize
-                )
-            }
-        };
+#[cfg(not(feature = "nightly_formatting_options"))]
+fn write_specified_argument_value(
+    output: &mut impl Write,
+    specifier: &Specifier,
+    value: &ArgumentValue<'_>,
+    width: Option<u16>,
+    precision: Option<u16>,
+) -> core::fmt::Result {
+    match (
+        specifier.alignment,
+        specifier.sign,
+        specifier.alternate_form,
+        specifier.pad_zero,
+        &specifier.precision,
+    ) {
+        (Alignment::Auto, true, true, true, Precision::Auto) => {
+            write!(output, "{:+#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, true, true, true, _) => {
+            write!(
+                output,
+                "{:+#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, true, true, false, Precision::Auto) => {
+            write!(output, "{:+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, true, true, false, _) => {
+            write!(
+                output,
+                "{:+#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, true, false, true, Precision::Auto) => {
+            write!(output, "{:+0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, true, false, true, _) => {
+            write!(
+                output,
+                "{:+0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, true, false, false, Precision::Auto) => {
+            write!(output, "{:+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, true, false, false, _) => {
+            write!(
+                output,
+                "{:+w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, false, true, true, Precision::Auto) => {
+            write!(output, "{:#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, false, true, true, _) => {
+            write!(
+                output,
+                "{:#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, false, true, false, Precision::Auto) => {
+            write!(output, "{:#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, false, true, false, _) => {
+            write!(
+                output,
+                "{:#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, false, false, true, Precision::Auto) => {
+            write!(output, "{:0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, false, false, true, _) => {
+            write!(
+                output,
+                "{:0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Auto, false, false, false, Precision::Auto) => {
+            write!(output, "{:w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Auto, false, false, false, _) => {
+            write!(
+                output,
+                "{:w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
 
-        if let Some(width) = width {
-            if specifier.alignment != Alignment::Auto && width > 0 && output.contains('ꙮ') {
-                *output = output.replace('ꙮ', &specifier.fill_character.to_string());
-            }
+        (Alignment::Left, true, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ<+#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, true, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ<+#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, true, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ<+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, true, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ<+#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, true, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ<+0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, true, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ<+0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, true, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ<+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, true, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ<+w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, false, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ<#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, false, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ<#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, false, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ<#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, false, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ<#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, false, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ<0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, false, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ<0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Left, false, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ<w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Left, false, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ<w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
         }
 
-        result
-    } else {
-        write!(output, "{}", value)
+        (Alignment::Center, true, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ^+#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, true, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ^+#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, true, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ^+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, true, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ^+#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, true, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ^+0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, true, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ^+0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, true, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ^+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, true, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ^+w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, false, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ^#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, false, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ^#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, false, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ^#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, false, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ^#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, false, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ^0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, false, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ^0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Center, false, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ^w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Center, false, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ^w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+
+        (Alignment::Right, true, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ>+#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, true, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ>+#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, true, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ>+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, true, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ>+#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, true, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ>+0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, true, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ>+0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, true, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ>+#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, true, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ>+w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, false, true, true, Precision::Auto) => {
+            write!(output, "{:ꙮ>#0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, false, true, true, _) => {
+            write!(
+                output,
+                "{:ꙮ>#0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, false, true, false, Precision::Auto) => {
+            write!(output, "{:ꙮ>#w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, false, true, false, _) => {
+            write!(
+                output,
+                "{:ꙮ>#w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, false, false, true, Precision::Auto) => {
+            write!(output, "{:ꙮ>0w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, false, false, true, _) => {
+            write!(
+                output,
+                "{:ꙮ>0w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
+        (Alignment::Right, false, false, false, Precision::Auto) => {
+            write!(output, "{:ꙮ>w$}", value, w = width.unwrap() as usize)
+        }
+        (Alignment::Right, false, false, false, _) => {
+            write!(
+                output,
+                "{:ꙮ>w$.p$}",
+                value,
+                w = width.unwrap() as usize,
+                p = precision.unwrap() as usize
+            )
+        }
     }
 }
@@ -1,5 +1,13 @@
-use crate::{ArgumentValue, Arguments, Error, ToArgumentKey, WidthOrPrecisionAmount};
-use core::fmt::{Binary, Debug, Display, LowerExp, LowerHex, Octal, Pointer, UpperExp, UpperHex};
+#[cfg(feature = "locale")]
+use crate::Locale;
+use crate::{
+    ArgumentValue, Arguments, AsDuration, Error, Escape, FormatDateTime, FormatterRegistry, Number,
+    OutputLimit, Quantity, ToArgumentKey, WidthOrPrecisionAmount,
+};
+use core::any::Any;
+use core::fmt::{
+    Binary, Debug, Display, Formatter, LowerExp, LowerHex, Octal, Pointer, UpperExp, UpperHex,
+};
 
 #[cfg(not(feature = "std"))]
 use alloc::string::String;
@@ -9,6 +17,7 @@ pub trait ArgumentsBuilder<'a> {
     fn format(self) -> Result<String, Error>;
     fn argument_value<K: ToArgumentKey>(self, key: K, value: ArgumentValue<'a>) -> Self;
     fn display<T: Display, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn number<T: Number, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn debug<T: Debug, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn binary<T: Binary, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn octal<T: Octal, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
@@ -22,6 +31,22 @@ pub trait ArgumentsBuilder<'a> {
         key: K,
         value: &'a T,
     ) -> Self;
+    fn bytes<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn binary_bytes<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn percent<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn duration<T: AsDuration, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn date_time<T: FormatDateTime, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn escape(self, escape: Escape) -> Self;
+    fn custom<T: Any, K: ToArgumentKey>(self, key: K, name: &'static str, value: &'a T) -> Self;
+    fn lazy<F: Fn(&mut Formatter) -> core::fmt::Result, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a F,
+    ) -> Self;
+    fn formatters(self, formatters: &'a FormatterRegistry) -> Self;
+    fn output_limit(self, output_limit: OutputLimit) -> Self;
+    #[cfg(feature = "locale")]
+    fn locale(self, locale: Locale) -> Self;
 }
 
 impl<'a> ArgumentsBuilder<'a> for Result<Arguments<'a>, Error> {
@@ -49,6 +74,16 @@ impl<'a> ArgumentsBuilder<'a> for Result<Arguments<'a>, Error> {
         Ok(args)
     }
 
+    fn number<T: Number, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Number(value))?;
+        Ok(args)
+    }
+
     fn debug<T: Debug, K: ToArgumentKey>(
         self,
         key: K,
@@ -138,12 +173,109 @@ impl<'a> ArgumentsBuilder<'a> for Result<Arguments<'a>, Error> {
         args.add_argument_value(key, ArgumentValue::WidthOrPrecisionAmount(value))?;
         Ok(args)
     }
+
+    fn bytes<T: Quantity, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Bytes(value))?;
+        Ok(args)
+    }
+
+    fn binary_bytes<T: Quantity, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::BinaryBytes(value))?;
+        Ok(args)
+    }
+
+    fn percent<T: Quantity, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Percent(value))?;
+        Ok(args)
+    }
+
+    fn duration<T: AsDuration, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Duration(value))?;
+        Ok(args)
+    }
+
+    fn date_time<T: FormatDateTime, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::DateTime(value))?;
+        Ok(args)
+    }
+
+    fn escape(self, escape: Escape) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.set_escape(escape);
+        Ok(args)
+    }
+
+    fn custom<T: Any, K: ToArgumentKey>(
+        self,
+        key: K,
+        name: &'static str,
+        value: &'a T,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Custom(name, value))?;
+        Ok(args)
+    }
+
+    fn lazy<F: Fn(&mut Formatter) -> core::fmt::Result, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a F,
+    ) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.add_argument_value(key, ArgumentValue::Lazy(value))?;
+        Ok(args)
+    }
+
+    fn formatters(self, formatters: &'a FormatterRegistry) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.set_formatters(formatters);
+        Ok(args)
+    }
+
+    fn output_limit(self, output_limit: OutputLimit) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.set_output_limit(output_limit);
+        Ok(args)
+    }
+
+    #[cfg(feature = "locale")]
+    fn locale(self, locale: Locale) -> Result<Arguments<'a>, Error> {
+        let mut args = self?;
+        args.set_locale(locale);
+        Ok(args)
+    }
 }
 
 /// Unchecked builder of the [`Arguments`][$crate::Arguments].
 pub trait UncheckedArgumentsBuilder<'a> {
     fn argument_value_unchecked<K: ToArgumentKey>(self, key: K, value: ArgumentValue<'a>) -> Self;
     fn display_unchecked<T: Display, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn number_unchecked<T: Number, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn debug_unchecked<T: Debug, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn binary_unchecked<T: Binary, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
     fn octal_unchecked<T: Octal, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
@@ -157,6 +289,23 @@ pub trait UncheckedArgumentsBuilder<'a> {
         key: K,
         value: &'a T,
     ) -> Self;
+    fn bytes_unchecked<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn binary_bytes_unchecked<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn percent_unchecked<T: Quantity, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn duration_unchecked<T: AsDuration, K: ToArgumentKey>(self, key: K, value: &'a T) -> Self;
+    fn date_time_unchecked<T: FormatDateTime, K: ToArgumentKey>(self, key: K, value: &'a T)
+        -> Self;
+    fn custom_unchecked<T: Any, K: ToArgumentKey>(
+        self,
+        key: K,
+        name: &'static str,
+        value: &'a T,
+    ) -> Self;
+    fn lazy_unchecked<F: Fn(&mut Formatter) -> core::fmt::Result, K: ToArgumentKey>(
+        self,
+        key: K,
+        value: &'a F,
+    ) -> Self;
 }
 
 impl<'a> UncheckedArgumentsBuilder<'a> for Arguments<'a> {
@@ -178,6 +327,15 @@ impl<'a> UncheckedArgumentsBuilder<'a> for Arguments<'a> {
         self
     }
 
+    fn number_unchecked<T: Number, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Number(value));
+        self
+    }
+
     fn debug_unchecked<T: Debug, K: ToArgumentKey>(
         mut self,
         key: K,
@@ -258,4 +416,68 @@ impl<'a> UncheckedArgumentsBuilder<'a> for Arguments<'a> {
         self.add_argument_value_unchecked(key, ArgumentValue::WidthOrPrecisionAmount(value));
         self
     }
+
+    fn bytes_unchecked<T: Quantity, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Bytes(value));
+        self
+    }
+
+    fn binary_bytes_unchecked<T: Quantity, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::BinaryBytes(value));
+        self
+    }
+
+    fn percent_unchecked<T: Quantity, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Percent(value));
+        self
+    }
+
+    fn duration_unchecked<T: AsDuration, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Duration(value));
+        self
+    }
+
+    fn date_time_unchecked<T: FormatDateTime, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::DateTime(value));
+        self
+    }
+
+    fn custom_unchecked<T: Any, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        name: &'static str,
+        value: &'a T,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Custom(name, value));
+        self
+    }
+
+    fn lazy_unchecked<F: Fn(&mut Formatter) -> core::fmt::Result, K: ToArgumentKey>(
+        mut self,
+        key: K,
+        value: &'a F,
+    ) -> Arguments<'a> {
+        self.add_argument_value_unchecked(key, ArgumentValue::Lazy(value));
+        self
+    }
 }
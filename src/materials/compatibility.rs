@@ -0,0 +1,156 @@
+use crate::{values::Type, ArgumentKey, ArgumentTypeRequirements, Template};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A difference between two templates found by [`Template::compatibility`],
+/// which prevents formatting one with the arguments of the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// The argument is used by the original, but not by the other template.
+    MissingKey(ArgumentKey),
+    /// The argument is used by the other, but not by the original template.
+    UnexpectedKey(ArgumentKey),
+    /// The argument is formatted with different traits, e.g. `{n:x}` and `{n}`.
+    /// Dynamic width and precision are reported separately.
+    TypeMismatch {
+        key: ArgumentKey,
+        expected: ArgumentTypeRequirements,
+        found: ArgumentTypeRequirements,
+    },
+    /// The argument is used as dynamic width or precision by the original, but not by the other template.
+    MissingWidthOrPrecision(ArgumentKey),
+    /// The argument is used as dynamic width or precision by the other, but not by the original template.
+    UnexpectedWidthOrPrecision(ArgumentKey),
+}
+
+impl core::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Incompatibility::MissingKey(key) => write!(f, "Missing argument '{}'", key),
+            Incompatibility::UnexpectedKey(key) => write!(f, "Unexpected argument '{}'", key),
+            Incompatibility::TypeMismatch {
+                key,
+                expected,
+                found,
+            } => {
+                write!(f, "Argument '{}' expected as ", key)?;
+                write_types(f, expected)?;
+                f.write_str(" but found as ")?;
+                write_types(f, found)
+            }
+            Incompatibility::MissingWidthOrPrecision(key) => {
+                write!(f, "Argument '{}' is not used as width or precision", key)
+            }
+            Incompatibility::UnexpectedWidthOrPrecision(key) => {
+                write!(
+                    f,
+                    "Argument '{}' is unexpectedly used as width or precision",
+                    key
+                )
+            }
+        }
+    }
+}
+
+fn write_types(
+    f: &mut core::fmt::Formatter<'_>,
+    requirements: &ArgumentTypeRequirements,
+) -> core::fmt::Result {
+    for (index, ty) in requirements.types().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        match ty {
+            Type::Display => f.write_str("{}")?,
+            ty => write!(f, "{{:{}}}", ty)?,
+        }
+    }
+    Ok(())
+}
+
+impl Template {
+    /// Compares the arguments of this template to the ones of the other template, e.g. a translation.
+    ///
+    /// Reports the keys used by only one of them, the keys formatted with different traits and
+    /// the dynamic width or precision keys used by only one of them. Partials are not resolved,
+    /// hence [`Template::resolve_partials`] should be called beforehand.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let source = Template::parse("{count:x} files in {dir}").unwrap();
+    /// let translation = Template::parse("{count} Dateien in {folder}").unwrap();
+    ///
+    /// let incompatibilities = source.compatibility(&translation);
+    /// assert_eq!(incompatibilities.len(), 3);
+    /// assert_eq!(
+    ///     incompatibilities[0].to_string(),
+    ///     "Argument 'count' expected as {:x} but found as {}"
+    /// );
+    /// assert_eq!(incompatibilities[1], Incompatibility::MissingKey(ArgumentKey::Name("dir".to_string())));
+    /// assert_eq!(incompatibilities[2], Incompatibility::UnexpectedKey(ArgumentKey::Name("folder".to_string())));
+    /// ```
+    pub fn compatibility(&self, other: &Template) -> Vec<Incompatibility> {
+        let mut incompatibilities = Vec::new();
+
+        for (key, expected) in &self.requirements {
+            let Some((_, found)) = other
+                .requirements
+                .iter()
+                .find(|(other_key, _)| other_key == key)
+            else {
+                incompatibilities.push(Incompatibility::MissingKey(key.clone()));
+                continue;
+            };
+
+            let (expected_types, found_types) = (
+                without_width_or_precision(expected),
+                without_width_or_precision(found),
+            );
+            if expected_types != found_types {
+                incompatibilities.push(Incompatibility::TypeMismatch {
+                    key: key.clone(),
+                    expected: expected_types,
+                    found: found_types,
+                });
+            }
+
+            match (
+                expected.width_or_precision_amount,
+                found.width_or_precision_amount,
+            ) {
+                (true, false) => {
+                    incompatibilities.push(Incompatibility::MissingWidthOrPrecision(key.clone()))
+                }
+                (false, true) => {
+                    incompatibilities.push(Incompatibility::UnexpectedWidthOrPrecision(key.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        for (key, _) in &other.requirements {
+            if !self
+                .requirements
+                .iter()
+                .any(|(self_key, _)| self_key == key)
+            {
+                incompatibilities.push(Incompatibility::UnexpectedKey(key.clone()));
+            }
+        }
+
+        incompatibilities
+    }
+
+    /// Whether the other template uses the same arguments in the same way, see [`Template::compatibility`].
+    pub fn is_compatible_with(&self, other: &Template) -> bool {
+        self.compatibility(other).is_empty()
+    }
+}
+
+fn without_width_or_precision(requirements: &ArgumentTypeRequirements) -> ArgumentTypeRequirements {
+    ArgumentTypeRequirements {
+        width_or_precision_amount: false,
+        ..requirements.clone()
+    }
+}
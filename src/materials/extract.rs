@@ -0,0 +1,307 @@
+use core::str::FromStr;
+
+use crate::{
+    values::{Alignment, Piece, Specifier, Type, Width},
+    ArgumentKey, Error, Template, ToArgumentKey,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+/// Values captured from a formatted string by [`Template::extract`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'s> {
+    captures: Vec<(ArgumentKey, Type, &'s str)>,
+}
+
+impl<'s> Captures<'s> {
+    /// The captured text of the argument, without its padding.
+    pub fn get<K: ToArgumentKey>(&self, key: K) -> Option<&'s str> {
+        let key = key.to_argument_key();
+        self.captures
+            .iter()
+            .find(|(captured_key, _, _)| captured_key == &key)
+            .map(|(_, _, value)| *value)
+    }
+
+    /// Iterates the captured texts in order of their placeholders.
+    pub fn iter(&self) -> impl Iterator<Item = (&ArgumentKey, &'s str)> {
+        self.captures.iter().map(|(key, _, value)| (key, *value))
+    }
+
+    /// Parses the captured text of the argument.
+    pub fn parse<T: FromStr, K: ToArgumentKey>(&self, key: K) -> Result<T, Error> {
+        let key = key.to_argument_key();
+        self.get(key.clone())
+            .ok_or(Error::ArgumentNotFound(key))?
+            .parse()
+            .map_err(|_| Error::UnexpectedArgumentValue)
+    }
+
+    /// Parses the captured text of the argument as integer,
+    /// using the radix of its placeholder type, e.g. `16` for `{:x}`.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("id={id:#x}").unwrap();
+    /// let captures = template.extract("id=0xff").unwrap();
+    /// assert_eq!(captures.parse_integer::<u8, _>("id").unwrap(), 255);
+    /// ```
+    pub fn parse_integer<T: TryFrom<i128>, K: ToArgumentKey>(&self, key: K) -> Result<T, Error> {
+        let key = key.to_argument_key();
+        let (_, ty, value) = self
+            .captures
+            .iter()
+            .find(|(captured_key, _, _)| captured_key == &key)
+            .ok_or(Error::ArgumentNotFound(key))?;
+
+        let (radix, prefix) = match ty {
+            Type::Binary => (2, "0b"),
+            Type::Octal => (8, "0o"),
+            Type::LowerHex | Type::UpperHex | Type::Pointer => (16, "0x"),
+            _ => (10, ""),
+        };
+        let value = value.strip_prefix('+').unwrap_or(value);
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let value = value.strip_prefix(prefix).unwrap_or(value);
+
+        let number =
+            i128::from_str_radix(value, radix).map_err(|_| Error::UnexpectedArgumentValue)?;
+        T::try_from(if negative { -number } else { number })
+            .map_err(|_| Error::UnexpectedArgumentValue)
+    }
+}
+
+impl Template {
+    /// Extracts the argument values from a string, which was formatted with this template.
+    ///
+    /// The literals of the template are used as anchors, while the type, fill, alignment
+    /// and width of the placeholders restrict what each placeholder may capture.
+    /// Each placeholder captures as few characters as possible, but only captures nothing
+    /// if there is no other match, e.g. `{}` formatted with an empty string.
+    /// A key which is used multiple times with the same type has to capture the same text.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("user={name} id={id:x} took {ms}ms").unwrap();
+    /// let captures = template.extract("user=tom id=1f took 42ms").unwrap();
+    /// assert_eq!(captures.get("name"), Some("tom"));
+    /// assert_eq!(captures.parse_integer::<u32, _>("id").unwrap(), 31);
+    /// assert_eq!(captures.parse::<u64, _>("ms").unwrap(), 42);
+    /// ```
+    pub fn extract<'s>(&self, input: &'s str) -> Result<Captures<'s>, Error> {
+        let mut captures = Vec::with_capacity(self.requirements.len());
+        if extract_pieces(
+            &self.pieces,
+            0,
+            input,
+            0,
+            &mut captures,
+            &mut BTreeSet::new(),
+        ) {
+            Ok(Captures { captures })
+        } else {
+            Err(Error::NoMatch)
+        }
+    }
+
+    /// Whether the string could have been formatted with this template.
+    pub fn is_match(&self, input: &str) -> bool {
+        self.extract(input).is_ok()
+    }
+}
+
+/// Placeholders which failed to match at a position, alongside the captured values
+/// of the keys which are used again from there on, since these have to be repeated.
+type Failures<'s> = BTreeSet<(usize, usize, Vec<&'s str>)>;
+
+fn extract_pieces<'s>(
+    pieces: &[Piece],
+    index: usize,
+    input: &'s str,
+    position: usize,
+    captures: &mut Vec<(ArgumentKey, Type, &'s str)>,
+    failures: &mut Failures<'s>,
+) -> bool {
+    let Some((piece, rest)) = pieces[index..].split_first() else {
+        return position == input.len();
+    };
+
+    match piece {
+        Piece::Literal(literal) => {
+            input[position..].starts_with(literal.as_str())
+                && extract_pieces(
+                    pieces,
+                    index + 1,
+                    input,
+                    position + literal.len(),
+                    captures,
+                    failures,
+                )
+        }
+        Piece::BracketOpen => {
+            input[position..].starts_with('{')
+                && extract_pieces(pieces, index + 1, input, position + 1, captures, failures)
+        }
+        Piece::BracketClose => {
+            input[position..].starts_with('}')
+                && extract_pieces(pieces, index + 1, input, position + 1, captures, failures)
+        }
+        Piece::Partial(_) => false,
+        #[cfg(feature = "message_format")]
+        Piece::Choice { .. } | Piece::Count(_) => false,
+        Piece::Argument {
+            key,
+            specifier,
+            filters,
+            ..
+        } => {
+            let ty = specifier.as_ref().map(|it| it.ty).unwrap_or(Type::Display);
+            // Filters may change any value, hence it is captured like a string
+            let filtered_specifier =
+                specifier
+                    .as_ref()
+                    .filter(|_| !filters.is_empty())
+                    .map(|specifier| Specifier {
+                        ty: Type::Display,
+                        ..specifier.clone()
+                    });
+            let capture_specifier = filtered_specifier.as_ref().or(specifier.as_ref());
+            let next_literal = match rest.first() {
+                Some(Piece::Literal(literal)) => Some(literal.as_str()),
+                _ => None,
+            };
+
+            // Without memoizing the failures, adjacent placeholders would try every split
+            // of the input, which takes exponential time
+            let repeated = captures
+                .iter()
+                .filter(|(captured_key, captured_ty, _)| {
+                    is_used(&pieces[index..], captured_key, captured_ty)
+                })
+                .map(|(_, _, captured)| *captured)
+                .collect();
+            let state = (index, position, repeated);
+            if failures.contains(&state) {
+                return false;
+            }
+
+            // The empty capture comes last, hence it is only used if there is no other match
+            let ends = input[position..]
+                .char_indices()
+                .map(|(offset, chr)| position + offset + chr.len_utf8())
+                .chain(core::iter::once(position));
+            for end in ends {
+                if next_literal.is_some_and(|literal| !input[end..].starts_with(literal)) {
+                    continue;
+                }
+                let Some(value) = capture(&input[position..end], capture_specifier) else {
+                    continue;
+                };
+
+                let previous_capture = captures.iter().find(|(captured_key, captured_ty, _)| {
+                    captured_key == key && *captured_ty == ty
+                });
+                if previous_capture.is_some_and(|(_, _, captured)| *captured != value) {
+                    continue;
+                }
+
+                captures.push((key.clone(), ty, value));
+                if extract_pieces(pieces, index + 1, input, end, captures, failures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            failures.insert(state);
+            false
+        }
+    }
+}
+
+/// Whether one of the pieces is a placeholder of the key and type.
+fn is_used(pieces: &[Piece], key: &ArgumentKey, ty: &Type) -> bool {
+    pieces.iter().any(|piece| match piece {
+        Piece::Argument {
+            key: piece_key,
+            specifier,
+            ..
+        } => piece_key == key && specifier.as_ref().map_or(Type::Display, |it| it.ty) == *ty,
+        _ => false,
+    })
+}
+
+/// Strips the padding of the text and checks whether it could be the output of the specifier.
+fn capture<'s>(text: &'s str, specifier: Option<&Specifier>) -> Option<&'s str> {
+    let Some(specifier) = specifier else {
+        return Some(text);
+    };
+
+    let value = match specifier.alignment {
+        Alignment::Left => text.trim_end_matches(specifier.fill_character),
+        Alignment::Right => text.trim_start_matches(specifier.fill_character),
+        Alignment::Center => text.trim_matches(specifier.fill_character),
+        // Numbers are padded with spaces on the left, other values on the right
+        Alignment::Auto => text.trim_matches(' '),
+    };
+    if value.is_empty() && specifier.ty != Type::Display && specifier.ty != Type::Debug {
+        return None;
+    }
+    if value.len() != text.len() {
+        if let Width::Fixed(width) = specifier.width {
+            if text.chars().count() != width as usize {
+                return None;
+            }
+        }
+    }
+
+    let unsigned = match value.strip_prefix(['+', '-']) {
+        Some(unsigned) if specifier.ty != Type::Display && specifier.ty != Type::Debug => unsigned,
+        _ => value,
+    };
+    let digits = |prefix: &str, is_digit: fn(char) -> bool| {
+        let digits = match (
+            specifier.alternate_form || specifier.ty == Type::Pointer,
+            unsigned.strip_prefix(prefix),
+        ) {
+            (true, Some(digits)) => digits,
+            (true, None) => return false,
+            (false, _) => unsigned,
+        };
+        !digits.is_empty() && digits.chars().all(is_digit)
+    };
+
+    let is_valid = match specifier.ty {
+        Type::Display
+        | Type::Debug
+        | Type::WidthOrPrecisionAmount
+        | Type::Bytes
+        | Type::BinaryBytes
+        | Type::Percent
+        | Type::Duration
+        | Type::DateTime
+        | Type::Custom(_) => true,
+        Type::Binary => digits("0b", |chr| matches!(chr, '0' | '1')),
+        Type::Octal => digits("0o", |chr| matches!(chr, '0'..='7')),
+        Type::LowerHex | Type::Pointer => digits("0x", |chr| matches!(chr, '0'..='9' | 'a'..='f')),
+        Type::UpperHex => digits("0x", |chr| matches!(chr, '0'..='9' | 'A'..='F')),
+        Type::LowerExp => is_exp(unsigned, 'e'),
+        Type::UpperExp => is_exp(unsigned, 'E'),
+    };
+    is_valid.then_some(value)
+}
+
+fn is_exp(value: &str, exp: char) -> bool {
+    let Some((mantissa, exponent)) = value.split_once(exp) else {
+        return false;
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, "0"));
+    let exponent = exponent.strip_prefix('-').unwrap_or(exponent);
+    [integer, fraction, exponent]
+        .iter()
+        .all(|part| !part.is_empty() && part.chars().all(|chr| chr.is_ascii_digit()))
+}
@@ -0,0 +1,671 @@
+use crate::{
+    values::{ChoiceKind, Piece, PluralCategory, Precision, Specifier},
+    ArgumentKey, ArgumentValue, Error, Locale, Partials, Template, ToArgumentKey,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Maximum amount of placeables nested in each other, which bounds the recursion of the parser.
+const MAX_PLACEABLE_DEPTH: usize = 32;
+
+/// Messages and terms of a [Project Fluent](https://projectfluent.org) resource (`.ftl`),
+/// compiled into templates.
+///
+/// Every message and term is available by its name, e.g. `hello` or `-brand`, and every
+/// attribute by the name of its message followed by the attribute, e.g. `login.placeholder`.
+///
+/// Supported are variables (`{ $name }`), message and term references, string and number
+/// literals, select expressions and `NUMBER($value, minimumFractionDigits: 2)`, where the
+/// maximum fraction digits are mapped to the precision, whose trailing zeros are trimmed down
+/// to the minimum, and the integer digits are padded with zeros.
+/// Terms with arguments, other functions and placeables nested deeper than 32 levels are rejected.
+/// ```rust
+/// use dfmt::*;
+///
+/// let resource = FluentResource::parse(
+///     r#"
+/// -brand = Firefox
+/// ## Shown after the download
+/// downloaded = { $count ->
+///         [one] One file
+///        *[other] { $count } files
+///     } downloaded by { -brand }.
+///     .title = Total: { NUMBER($total, minimumFractionDigits: 2) } MB
+/// "#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     resource
+///         .format("downloaded", vec![(&"count", ArgumentValue::Display(&3))])
+///         .unwrap(),
+///     "3 files downloaded by Firefox."
+/// );
+/// assert_eq!(
+///     resource
+///         .format("downloaded.title", vec![(&"total", ArgumentValue::Number(&1.5))])
+///         .unwrap(),
+///     "Total: 1.50 MB"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FluentResource {
+    templates: BTreeMap<String, Template>,
+    locale: Locale,
+}
+
+impl FluentResource {
+    /// Parses the resource and compiles all of its messages, terms and attributes.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+            depth: 0,
+        };
+        Ok(Self {
+            templates: parser.resource()?,
+            locale: Locale::default(),
+        })
+    }
+
+    /// Builder to set the locale, whose plural rules select the variants of select expressions.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Looks up the template of a message, term or attribute.
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates.get(name)
+    }
+
+    /// Whether a message, term or attribute with this name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Iterates the names of all messages, terms and attributes.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Formats the message with the values, while resolving references to other messages and terms.
+    pub fn format(
+        &self,
+        name: &str,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<String, Error> {
+        let template = self
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut arguments = template.arguments();
+        arguments.set_locale(self.locale);
+        for (key, argument_value) in argument_values {
+            arguments.add_argument_value(key.to_argument_key(), argument_value)?;
+        }
+        arguments
+            .format_with_partials(self)
+            .map_err(|error| Error::InTemplate(name.to_string(), Box::new(error)))
+    }
+}
+
+impl Partials for FluentResource {
+    fn partial(&self, name: &str) -> Option<&Template> {
+        self.get(name)
+    }
+}
+
+/// Part of a pattern before the common indentation is removed.
+enum Element<'s> {
+    Text(&'s str),
+    Newline,
+    Indent(usize),
+    Piece(Piece),
+}
+
+struct Parser<'s> {
+    source: &'s str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn resource(&mut self) -> Result<BTreeMap<String, Template>, Error> {
+        let mut templates = BTreeMap::new();
+        while let Some(chr) = self.peek() {
+            match chr {
+                b'#' => {
+                    while !matches!(self.peek(), None | Some(b'\n')) {
+                        self.position += 1;
+                    }
+                }
+                b'\n' | b'\r' => self.position += 1,
+                b' ' => {
+                    self.blank_inline();
+                    if !matches!(self.peek(), None | Some(b'\n' | b'\r')) {
+                        return Err(self.error());
+                    }
+                }
+                b'-' => {
+                    self.position += 1;
+                    let name = format!("-{}", self.identifier()?);
+                    self.entry(name, &mut templates)?;
+                }
+                b'a'..=b'z' | b'A'..=b'Z' => {
+                    let name = self.identifier()?.to_string();
+                    self.entry(name, &mut templates)?;
+                }
+                _ => return Err(self.error()),
+            }
+        }
+        Ok(templates)
+    }
+
+    fn entry(
+        &mut self,
+        name: String,
+        templates: &mut BTreeMap<String, Template>,
+    ) -> Result<(), Error> {
+        self.blank_inline();
+        self.expect(b'=')?;
+        self.blank_inline();
+        let value = self.pattern()?;
+
+        let mut has_attributes = false;
+        loop {
+            let position = self.position;
+            self.blank();
+            if self.peek() != Some(b'.') || self.column() == 0 {
+                self.position = position;
+                break;
+            }
+            self.position += 1;
+            let attribute = self.identifier()?;
+            self.blank_inline();
+            self.expect(b'=')?;
+            self.blank_inline();
+            let pattern = self.pattern()?;
+            if pattern.is_empty() {
+                return Err(self.error());
+            }
+            templates.insert(format!("{}.{}", name, attribute), template(pattern));
+            has_attributes = true;
+        }
+
+        // Terms require a value, messages either a value or attributes
+        if value.is_empty() && (!has_attributes || name.starts_with('-')) {
+            return Err(self.error());
+        }
+        if !value.is_empty() {
+            templates.insert(name, template(value));
+        }
+
+        self.blank_inline();
+        match self.peek() {
+            None | Some(b'\n' | b'\r') => Ok(()),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Parses text and placeables, including indented continuation lines.
+    fn pattern(&mut self) -> Result<Vec<Piece>, Error> {
+        let mut elements = Vec::new();
+        loop {
+            let start = self.position;
+            while !matches!(self.peek(), None | Some(b'\n' | b'\r' | b'{' | b'}')) {
+                self.position += 1;
+            }
+            if start < self.position {
+                elements.push(Element::Text(&self.source[start..self.position]));
+            }
+
+            match self.peek() {
+                Some(b'{') => {
+                    self.position += 1;
+                    elements.push(Element::Piece(self.placeable()?));
+                }
+                Some(b'\n' | b'\r') => {
+                    let position = self.position;
+                    let mut newlines = 0;
+                    let mut indent = 0;
+                    while self.line_end() {
+                        newlines += 1;
+                        indent = self.blank_inline();
+                    }
+
+                    // Indented lines continue the pattern, unless they start a variant, attribute or the end of a select
+                    if indent > 0 && !matches!(self.peek(), None | Some(b'[' | b'*' | b'.' | b'}'))
+                    {
+                        elements.extend((0..newlines).map(|_| Element::Newline));
+                        elements.push(Element::Indent(indent));
+                    } else {
+                        self.position = position;
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        Ok(dedent(elements))
+    }
+
+    /// Parses a placeable after its opening bracket.
+    fn placeable(&mut self) -> Result<Piece, Error> {
+        if self.depth == MAX_PLACEABLE_DEPTH {
+            return Err(Error::NestingTooDeep(MAX_PLACEABLE_DEPTH));
+        }
+        self.depth += 1;
+        let piece = self.nested_placeable();
+        self.depth -= 1;
+        piece
+    }
+
+    fn nested_placeable(&mut self) -> Result<Piece, Error> {
+        self.blank();
+        let expression = self.inline_expression()?;
+        self.blank();
+
+        let piece = if self.source[self.position..].starts_with("->") {
+            self.position += 2;
+            let key = match expression {
+                Piece::Argument { key, .. } => key,
+                _ => return Err(self.error()),
+            };
+            let choice = self.variants(key)?;
+            self.blank();
+            choice
+        } else {
+            expression
+        };
+
+        self.expect(b'}')?;
+        Ok(piece)
+    }
+
+    fn inline_expression(&mut self) -> Result<Piece, Error> {
+        match self.peek() {
+            Some(b'"') => self.string_literal(),
+            Some(b'0'..=b'9') => Ok(Piece::Literal(self.number_literal()?.to_string())),
+            Some(b'-') if self.peek_at(1).is_some_and(|it| it.is_ascii_digit()) => {
+                Ok(Piece::Literal(self.number_literal()?.to_string()))
+            }
+            Some(b'$') => {
+                self.position += 1;
+                Ok(variable(
+                    ArgumentKey::Name(self.identifier()?.to_string()),
+                    None,
+                ))
+            }
+            Some(b'-') => {
+                self.position += 1;
+                let name = format!("-{}", self.reference()?);
+                let position = self.position;
+                self.blank();
+                if self.peek() == Some(b'(') {
+                    // Terms with arguments are not supported
+                    return Err(self.error());
+                }
+                self.position = position;
+                Ok(Piece::Partial(name))
+            }
+            Some(b'a'..=b'z' | b'A'..=b'Z') => {
+                let name = self.reference()?;
+                if self.peek() == Some(b'(') {
+                    self.position += 1;
+                    return self.function(name);
+                }
+                Ok(Piece::Partial(name.to_string()))
+            }
+            Some(b'{') => {
+                self.position += 1;
+                self.placeable()
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Parses `NUMBER($value, option: 1)` after its opening parenthesis.
+    fn function(&mut self, name: &str) -> Result<Piece, Error> {
+        if name != "NUMBER" {
+            return Err(self.error());
+        }
+
+        self.blank();
+        let key = match self.inline_expression()? {
+            Piece::Argument { key, .. } => key,
+            _ => return Err(self.error()),
+        };
+
+        let (mut minimum_fraction_digits, mut maximum_fraction_digits) = (None, None);
+        let mut minimum_integer_digits = None;
+        loop {
+            self.blank();
+            match self.peek() {
+                Some(b')') => {
+                    self.position += 1;
+                    break;
+                }
+                Some(b',') => {
+                    self.position += 1;
+                    self.blank();
+                }
+                _ => return Err(self.error()),
+            }
+
+            let option = self.identifier()?;
+            self.blank();
+            self.expect(b':')?;
+            self.blank();
+            let value = match self.peek() {
+                Some(b'"') => match self.string_literal()? {
+                    Piece::Literal(value) => value,
+                    _ => unreachable!("string literals are literals"),
+                },
+                _ => self.number_literal()?.to_string(),
+            };
+
+            // Unknown options are ignored, just like Fluent does
+            let digits = || value.parse::<u16>().ok();
+            match option {
+                "minimumFractionDigits" => minimum_fraction_digits = digits(),
+                "maximumFractionDigits" => maximum_fraction_digits = digits(),
+                "minimumIntegerDigits" => minimum_integer_digits = digits(),
+                _ => {}
+            }
+        }
+
+        // Like Intl, the maximum defaults to at least three digits and trailing zeros are trimmed
+        let fraction_digits = match (minimum_fraction_digits, maximum_fraction_digits) {
+            (None, None) => None,
+            (Some(minimum), Some(maximum)) if minimum > maximum => return Err(self.error()),
+            (minimum, maximum) => {
+                let minimum = minimum.unwrap_or(0);
+                Some((minimum, maximum.unwrap_or(minimum.max(3))))
+            }
+        };
+        let mut specifier = Specifier {
+            min_fraction_digits: fraction_digits.map(|(minimum, _)| minimum),
+            min_integer_digits: minimum_integer_digits,
+            ..Specifier::default()
+        };
+        if let Some((_, maximum)) = fraction_digits {
+            specifier = specifier.precision(Precision::Fixed(maximum));
+        }
+
+        let is_plain = fraction_digits.is_none() && minimum_integer_digits.is_none();
+        Ok(variable(key, (!is_plain).then_some(specifier)))
+    }
+
+    /// Parses the variants of a select expression after its `->`.
+    fn variants(&mut self, key: ArgumentKey) -> Result<Piece, Error> {
+        let mut variants: Vec<(String, bool, Vec<Piece>)> = Vec::new();
+        let mut default = None;
+        loop {
+            self.blank();
+            if matches!(self.peek(), None | Some(b'}')) {
+                break;
+            }
+
+            if self.peek() == Some(b'*') {
+                if default.is_some() {
+                    return Err(self.error());
+                }
+                default = Some(variants.len());
+                self.position += 1;
+            }
+            self.expect(b'[')?;
+            self.blank();
+            let is_number = matches!(self.peek(), Some(b'0'..=b'9' | b'-'));
+            let variant_key = if is_number {
+                self.number_literal()?
+            } else {
+                self.identifier()?
+            };
+            self.blank();
+            self.expect(b']')?;
+            self.blank_inline();
+
+            let pattern = self.pattern()?;
+            variants.push((variant_key.to_string(), is_number, pattern));
+        }
+        let default = default.ok_or_else(|| self.error())?;
+
+        let kind = if variants.iter().all(|(variant_key, is_number, _)| {
+            *is_number || PluralCategory::parse(variant_key).is_some()
+        }) {
+            ChoiceKind::Plural
+        } else {
+            ChoiceKind::Select
+        };
+
+        // Values without a matching variant select the `other` arm
+        let default_arm = variants[default].2.clone();
+        let mut arms: Vec<(String, Vec<Piece>)> = variants
+            .into_iter()
+            .map(|(variant_key, is_number, pattern)| match kind {
+                ChoiceKind::Plural if is_number => (format!("={}", variant_key), pattern),
+                _ => (variant_key, pattern),
+            })
+            .collect();
+        if !arms.iter().any(|(selector, _)| selector == "other") {
+            arms.push(("other".to_string(), default_arm));
+        }
+
+        Ok(Piece::Choice { key, kind, arms })
+    }
+
+    fn string_literal(&mut self) -> Result<Piece, Error> {
+        self.expect(b'"')?;
+        let mut literal = String::new();
+        loop {
+            let start = self.position;
+            while !matches!(self.peek(), None | Some(b'"' | b'\\' | b'\n' | b'\r')) {
+                self.position += 1;
+            }
+            literal.push_str(&self.source[start..self.position]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(Piece::Literal(literal));
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let len = match self.peek() {
+                        Some(b'\\' | b'"') => {
+                            literal.push(self.peek().unwrap_or_default() as char);
+                            self.position += 1;
+                            continue;
+                        }
+                        Some(b'u') => 4,
+                        Some(b'U') => 6,
+                        _ => return Err(self.error()),
+                    };
+                    let digits = self
+                        .source
+                        .get(self.position + 1..self.position + 1 + len)
+                        .ok_or_else(|| self.error())?;
+                    let chr = u32::from_str_radix(digits, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| self.error())?;
+                    literal.push(chr);
+                    self.position += 1 + len;
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn number_literal(&mut self) -> Result<&'s str, Error> {
+        let start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        let digits = self.digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            if self.digits() == 0 {
+                return Err(self.error());
+            }
+        }
+        if digits == 0 {
+            return Err(self.error());
+        }
+        Ok(&self.source[start..self.position])
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while self.peek().is_some_and(|it| it.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn identifier(&mut self) -> Result<&'s str, Error> {
+        let start = self.position;
+        if !self.peek().is_some_and(|it| it.is_ascii_alphabetic()) {
+            return Err(self.error());
+        }
+        while self
+            .peek()
+            .is_some_and(|it| it.is_ascii_alphanumeric() || matches!(it, b'_' | b'-'))
+        {
+            self.position += 1;
+        }
+        Ok(&self.source[start..self.position])
+    }
+
+    /// Identifier with an optional attribute, e.g. `login.placeholder`.
+    fn reference(&mut self) -> Result<&'s str, Error> {
+        let start = self.position;
+        self.identifier()?;
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            self.identifier()?;
+        }
+        Ok(&self.source[start..self.position])
+    }
+
+    fn blank_inline(&mut self) -> usize {
+        let start = self.position;
+        while self.peek() == Some(b' ') {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn blank(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn line_end(&mut self) -> bool {
+        let rest = &self.source[self.position..];
+        let len = if rest.starts_with("\r\n") {
+            2
+        } else if rest.starts_with('\n') {
+            1
+        } else {
+            0
+        };
+        self.position += len;
+        len > 0
+    }
+
+    fn expect(&mut self, chr: u8) -> Result<(), Error> {
+        if self.peek() == Some(chr) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.source.as_bytes().get(self.position + offset).copied()
+    }
+
+    fn column(&self) -> usize {
+        let line_start = self.source[..self.position]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        self.position - line_start
+    }
+
+    fn error(&self) -> Error {
+        Error::FluentSyntax(self.source[..self.position].matches('\n').count() + 1)
+    }
+}
+
+/// Removes the common indentation of the continuation lines, the leading blank lines
+/// and the trailing whitespace of the pattern.
+fn dedent(elements: Vec<Element<'_>>) -> Vec<Piece> {
+    let common_indent = elements
+        .iter()
+        .filter_map(|element| match element {
+            Element::Indent(indent) => Some(*indent),
+            _ => None,
+        })
+        .min()
+        .unwrap_or_default();
+
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let leading_newlines = elements
+        .iter()
+        .take_while(|element| matches!(element, Element::Newline))
+        .count();
+    for element in elements.into_iter().skip(leading_newlines) {
+        match element {
+            Element::Text(value) => text.push_str(value),
+            Element::Newline => text.push('\n'),
+            Element::Indent(indent) => {
+                text.extend((common_indent..indent).map(|_| ' '));
+            }
+            Element::Piece(piece) => {
+                if !text.is_empty() {
+                    pieces.push(Piece::Literal(core::mem::take(&mut text)));
+                }
+                pieces.push(piece);
+            }
+        }
+    }
+
+    let trimmed = text.trim_end_matches([' ', '\n', '\r']);
+    if !trimmed.is_empty() {
+        pieces.push(Piece::Literal(trimmed.to_string()));
+    }
+    pieces
+}
+
+fn template(pieces: Vec<Piece>) -> Template {
+    let spans = vec![None; pieces.len()];
+    Template::from_pieces(pieces, spans, None).optimize()
+}
+
+fn variable(key: ArgumentKey, specifier: Option<Specifier>) -> Piece {
+    Piece::Argument {
+        key,
+        specifier,
+        implicit_key: false,
+        implicit_precision: false,
+        filters: Vec::new(),
+    }
+}
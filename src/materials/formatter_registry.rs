@@ -0,0 +1,120 @@
+use core::{any::Any, fmt::Write};
+
+use crate::Specifier;
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Formatter of a custom type, which receives the value, the specifier and the output.
+/// Dynamic width and precision are already resolved into fixed ones.
+pub type CustomFormatter =
+    dyn Fn(&dyn Any, &Specifier, &mut dyn Write) -> core::fmt::Result + Send + Sync;
+
+/// Named formatters for domain types, which the std formatting traits can't express.
+///
+/// Templates parsed by [`Template::parse_with_formatters`][crate::Template::parse_with_formatters]
+/// accept the names as type, e.g. `{amount:money}` or `{size:>10bytes}`.
+/// The values are passed as [`ArgumentValue::Custom`][crate::ArgumentValue::Custom] and formatted
+/// with the formatters set by [`Arguments::set_formatters`][crate::Arguments::set_formatters].
+/// ```rust
+/// use dfmt::*;
+///
+/// let formatters = FormatterRegistry::new().formatter("bytes", |value, specifier, output| {
+///     let Some(bytes) = value.downcast_ref::<u64>() else {
+///         return Err(core::fmt::Error);
+///     };
+///     let text = if *bytes < 1024 {
+///         format!("{} B", bytes)
+///     } else {
+///         format!("{:.1} KiB", *bytes as f64 / 1024.0)
+///     };
+///     let width = match specifier.width {
+///         Width::Fixed(width) => width as usize,
+///         Width::Dynamic(_) => 0,
+///     };
+///     write!(output, "{:>w$}", text, w = width)
+/// });
+///
+/// let template = Template::parse_with_formatters("Size: {size:10bytes}", &formatters).unwrap();
+/// let size = 2048_u64;
+/// assert_eq!(
+///     template
+///         .arguments()
+///         .builder()
+///         .formatters(&formatters)
+///         .custom("size", "bytes", &size)
+///         .format()
+///         .unwrap(),
+///     "Size:    2.0 KiB"
+/// );
+/// ```
+#[derive(Default)]
+pub struct FormatterRegistry {
+    formatters: BTreeMap<&'static str, Box<CustomFormatter>>,
+}
+
+impl FormatterRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder to add a formatter, see [`FormatterRegistry::insert`].
+    pub fn formatter<F>(mut self, name: &'static str, formatter: F) -> Self
+    where
+        F: Fn(&dyn Any, &Specifier, &mut dyn Write) -> core::fmt::Result + Send + Sync + 'static,
+    {
+        self.insert(name, formatter);
+        self
+    }
+
+    /// Adds or replaces a formatter.
+    ///
+    /// The name is matched after the precision of a specifier.
+    ///
+    /// # Panics
+    ///
+    /// If the name is a specifier on its own, e.g. `x`, `e` or `?`,
+    /// because it would change the meaning of the std types.
+    pub fn insert<F>(&mut self, name: &'static str, formatter: F)
+    where
+        F: Fn(&dyn Any, &Specifier, &mut dyn Write) -> core::fmt::Result + Send + Sync + 'static,
+    {
+        if Specifier::parse(name, &mut 0).is_ok() {
+            panic!("the formatter name '{}' shadows a specifier", name);
+        }
+        self.formatters.insert(name, Box::new(formatter));
+    }
+
+    /// Gets the formatter of the name.
+    pub fn get(&self, name: &str) -> Option<&CustomFormatter> {
+        self.formatters
+            .get(name)
+            .map(|formatter| formatter.as_ref())
+    }
+
+    /// Whether a formatter of the name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.formatters.contains_key(name)
+    }
+
+    /// The names of the formatters.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.formatters.keys().copied()
+    }
+
+    /// Returns the registered name equal to the text.
+    pub(crate) fn name(&self, text: &str) -> Option<&'static str> {
+        self.formatters.get_key_value(text).map(|(name, _)| *name)
+    }
+}
+
+impl core::fmt::Debug for FormatterRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FormatterRegistry")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,677 @@
+use crate::{ArgumentValue, Error, Template, ToArgumentKey};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Separates the context from the message id within the keys, just like in `.mo` files.
+const CONTEXT_SEPARATOR: char = '\u{4}';
+
+/// Translations of a GNU gettext catalog (`.po` or `.mo`), whose message strings are templates.
+///
+/// Untranslated and fuzzy messages fall back to the message id, which is parsed as template as well.
+/// Plural forms are selected by the `Plural-Forms` header, which defaults to the English rule.
+/// ```rust
+/// use dfmt::*;
+///
+/// let catalog = Catalog::parse_po(
+///     r#"
+/// msgid ""
+/// msgstr "Plural-Forms: nplurals=2; plural=(n != 1);\n"
+///
+/// msgid "Hello {name}!"
+/// msgstr "Hallo {name}!"
+///
+/// msgid "{n} file"
+/// msgid_plural "{n} files"
+/// msgstr[0] "{n} Datei"
+/// msgstr[1] "{n} Dateien"
+/// "#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     catalog
+///         .tr("Hello {name}!", vec![(&"name", ArgumentValue::Display(&"Tom"))])
+///         .unwrap(),
+///     "Hallo Tom!"
+/// );
+/// assert_eq!(
+///     catalog
+///         .tr_plural("{n} file", "{n} files", 3, vec![(&"n", ArgumentValue::Display(&3))])
+///         .unwrap(),
+///     "3 Dateien"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: BTreeMap<String, Vec<Template>>,
+    plural_forms: PluralForms,
+}
+
+impl Catalog {
+    /// Parses the text format of gettext catalogs.
+    pub fn parse_po(source: &str) -> Result<Self, Error> {
+        let mut catalog = Self::default();
+        let mut entry = PoEntry::default();
+        let mut field = None;
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            let syntax_error = || Error::PoSyntax(line_number);
+
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if !entry.translations.is_empty() {
+                    catalog.add_po_entry(core::mem::take(&mut entry))?;
+                    field = None;
+                }
+                if let Some(flags) = comment.strip_prefix(',') {
+                    entry.is_fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+                }
+                continue;
+            }
+            if line.starts_with('"') {
+                let value = unescape(line).ok_or_else(syntax_error)?;
+                match field {
+                    Some(Field::Context) => entry.context.get_or_insert_with(String::new),
+                    Some(Field::Id) => &mut entry.id,
+                    // Only the message id is used for the lookup
+                    Some(Field::IdPlural) => continue,
+                    Some(Field::Translation) => {
+                        &mut entry.translations.last_mut().ok_or_else(syntax_error)?.1
+                    }
+                    None => return Err(syntax_error()),
+                }
+                .push_str(&value);
+                continue;
+            }
+
+            let (keyword, value) = line.split_once([' ', '\t']).ok_or_else(syntax_error)?;
+            let value = unescape(value.trim()).ok_or_else(syntax_error)?;
+            match keyword {
+                "msgctxt" | "msgid" => {
+                    if !entry.translations.is_empty() {
+                        catalog.add_po_entry(core::mem::take(&mut entry))?;
+                    }
+                    if keyword == "msgctxt" {
+                        entry.context = Some(value);
+                        field = Some(Field::Context);
+                    } else {
+                        entry.id = value;
+                        field = Some(Field::Id);
+                    }
+                }
+                "msgid_plural" if matches!(field, Some(Field::Id | Field::IdPlural)) => {
+                    field = Some(Field::IdPlural);
+                }
+                _ => {
+                    let plural_index = match keyword.strip_prefix("msgstr") {
+                        Some("") => 0,
+                        Some(index) => index
+                            .strip_prefix('[')
+                            .and_then(|index| index.strip_suffix(']'))
+                            .and_then(|index| index.parse::<usize>().ok())
+                            .ok_or_else(syntax_error)?,
+                        None => return Err(syntax_error()),
+                    };
+                    if field.is_none() || plural_index != entry.translations.len() {
+                        return Err(syntax_error());
+                    }
+                    entry.translations.push((plural_index, value));
+                    field = Some(Field::Translation);
+                }
+            }
+        }
+
+        if !entry.translations.is_empty() {
+            catalog.add_po_entry(entry)?;
+        } else if field.is_some() {
+            return Err(Error::PoSyntax(source.lines().count()));
+        }
+        Ok(catalog)
+    }
+
+    /// Parses the binary format of gettext catalogs in either byte order.
+    pub fn parse_mo(bytes: &[u8]) -> Result<Self, Error> {
+        let is_big_endian = match bytes.get(..4) {
+            Some([0xde, 0x12, 0x04, 0x95]) => false,
+            Some([0x95, 0x04, 0x12, 0xde]) => true,
+            _ => return Err(Error::InvalidMo),
+        };
+        let read_u32 = |offset: usize| -> Result<usize, Error> {
+            let word: [u8; 4] = bytes
+                .get(offset..offset.saturating_add(4))
+                .and_then(|word| word.try_into().ok())
+                .ok_or(Error::InvalidMo)?;
+            Ok(if is_big_endian {
+                u32::from_be_bytes(word)
+            } else {
+                u32::from_le_bytes(word)
+            } as usize)
+        };
+        let read_string = |descriptor: usize| -> Result<&str, Error> {
+            let (len, offset) = (
+                read_u32(descriptor)?,
+                read_u32(descriptor.saturating_add(4))?,
+            );
+            bytes
+                .get(offset..offset.checked_add(len).ok_or(Error::InvalidMo)?)
+                .and_then(|string| core::str::from_utf8(string).ok())
+                .ok_or(Error::InvalidMo)
+        };
+
+        let count = read_u32(8)?;
+        let (originals, translations) = (read_u32(12)?, read_u32(16)?);
+
+        let mut catalog = Self::default();
+        for index in 0..count {
+            let original = read_string(originals.saturating_add(index.saturating_mul(8)))?;
+            let translation = read_string(translations.saturating_add(index.saturating_mul(8)))?;
+
+            // Plural entries contain the plural message id after a null byte
+            let id = original.split('\0').next().unwrap_or_default();
+            if id.is_empty() {
+                catalog.plural_forms = PluralForms::from_header(translation)?;
+                continue;
+            }
+            catalog.add(id.to_string(), translation.split('\0'))?;
+        }
+        Ok(catalog)
+    }
+
+    /// Translates the message, or formats the message id if it is not translated.
+    pub fn tr(
+        &self,
+        msgid: &str,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<String, Error> {
+        match self.messages.get(msgid) {
+            Some(translations) => format(msgid, &translations[0], argument_values),
+            None => format(msgid, &parse(msgid, msgid)?, argument_values),
+        }
+    }
+
+    /// Translates the message within the context (`msgctxt`),
+    /// or formats the message id if it is not translated.
+    pub fn tr_context(
+        &self,
+        context: &str,
+        msgid: &str,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<String, Error> {
+        let key = context_key(context, msgid);
+        match self.messages.get(&key) {
+            Some(translations) => format(msgid, &translations[0], argument_values),
+            None => format(msgid, &parse(msgid, msgid)?, argument_values),
+        }
+    }
+
+    /// Translates the plural form of the message for the count `n`, or formats
+    /// the message id for `n == 1` and the plural message id otherwise if it is not translated.
+    ///
+    /// The count is only used to select the form, it still has to be passed as argument to be formatted.
+    pub fn tr_plural(
+        &self,
+        msgid: &str,
+        msgid_plural: &str,
+        n: u64,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<String, Error> {
+        let translation = self
+            .messages
+            .get(msgid)
+            .and_then(|translations| translations.get(self.plural_index(n)));
+        match translation {
+            Some(translation) => format(msgid, translation, argument_values),
+            None => {
+                let fallback = if n == 1 { msgid } else { msgid_plural };
+                format(msgid, &parse(msgid, fallback)?, argument_values)
+            }
+        }
+    }
+
+    /// Index of the plural form for the count `n`, as selected by the `Plural-Forms` header.
+    pub fn plural_index(&self, n: u64) -> usize {
+        self.plural_forms.expression.evaluate(n) as usize
+    }
+
+    /// Amount of plural forms, as declared by the `Plural-Forms` header.
+    pub fn plural_count(&self) -> usize {
+        self.plural_forms.count
+    }
+
+    /// Whether the message is translated.
+    pub fn contains(&self, msgid: &str) -> bool {
+        self.messages.contains_key(msgid)
+    }
+
+    /// Amount of translated messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether no message is translated.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    fn add_po_entry(&mut self, entry: PoEntry) -> Result<(), Error> {
+        if entry.id.is_empty() && entry.context.is_none() {
+            let header = entry.translations.first().map_or("", |(_, header)| header);
+            self.plural_forms = PluralForms::from_header(header)?;
+            return Ok(());
+        }
+        if entry.is_fuzzy {
+            return Ok(());
+        }
+
+        let key = match &entry.context {
+            Some(context) => context_key(context, &entry.id),
+            None => entry.id,
+        };
+        self.add(
+            key,
+            entry
+                .translations
+                .iter()
+                .map(|(_, translation)| translation.as_str()),
+        )
+    }
+
+    fn add<'a>(
+        &mut self,
+        key: String,
+        translations: impl Iterator<Item = &'a str>,
+    ) -> Result<(), Error> {
+        let translations = translations.collect::<Vec<_>>();
+        // Messages without any translation fall back to their message id
+        if translations
+            .iter()
+            .all(|translation| translation.is_empty())
+        {
+            return Ok(());
+        }
+
+        let msgid = key
+            .split_once(CONTEXT_SEPARATOR)
+            .map_or(key.as_str(), |(_, msgid)| msgid);
+        let templates = translations
+            .into_iter()
+            .map(|translation| parse(msgid, translation))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.messages.insert(key, templates);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Catalog {
+    /// Loads a `.mo` file, or any other file as `.po` file.
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|error| Error::io(path, &error))?;
+        if path.extension().is_some_and(|extension| extension == "mo") {
+            Self::parse_mo(&bytes)
+        } else {
+            let source = core::str::from_utf8(&bytes).map_err(|error| {
+                Error::io(
+                    path,
+                    &std::io::Error::new(std::io::ErrorKind::InvalidData, error),
+                )
+            })?;
+            Self::parse_po(source)
+        }
+    }
+}
+
+fn context_key(context: &str, msgid: &str) -> String {
+    let mut key = String::with_capacity(context.len() + 1 + msgid.len());
+    key.push_str(context);
+    key.push(CONTEXT_SEPARATOR);
+    key.push_str(msgid);
+    key
+}
+
+fn parse(msgid: &str, template: &str) -> Result<Template, Error> {
+    Template::parse(template).map_err(|error| Error::InTemplate(msgid.to_string(), Box::new(error)))
+}
+
+fn format(
+    msgid: &str,
+    template: &Template,
+    argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+) -> Result<String, Error> {
+    let mut arguments = template.arguments();
+    for (key, argument_value) in argument_values {
+        arguments.add_argument_value(key.to_argument_key(), argument_value)?;
+    }
+    arguments
+        .format()
+        .map_err(|error| Error::InTemplate(msgid.to_string(), Box::new(error)))
+}
+
+#[derive(Debug, Default)]
+struct PoEntry {
+    context: Option<String>,
+    id: String,
+    translations: Vec<(usize, String)>,
+    is_fuzzy: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Translation,
+}
+
+/// Unescapes a quoted C string literal.
+fn unescape(quoted: &str) -> Option<String> {
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(chr) = chars.next() {
+        match chr {
+            '"' => return None,
+            '\\' => result.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                'a' => '\u{7}',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'v' => '\u{b}',
+                chr @ ('\\' | '"' | '\'' | '?') => chr,
+                _ => return None,
+            }),
+            chr => result.push(chr),
+        }
+    }
+    Some(result)
+}
+
+/// Amount of plural forms and the C expression selecting the form, e.g. `nplurals=2; plural=(n != 1);`.
+#[derive(Debug, Clone)]
+struct PluralForms {
+    count: usize,
+    expression: Expression,
+}
+
+impl Default for PluralForms {
+    fn default() -> Self {
+        Self {
+            count: 2,
+            expression: Expression::Binary(
+                Operator::NotEqual,
+                Box::new(Expression::N),
+                Box::new(Expression::Number(1)),
+            ),
+        }
+    }
+}
+
+impl PluralForms {
+    fn from_header(header: &str) -> Result<Self, Error> {
+        let Some(plural_forms) = header
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Plural-Forms:"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let error =
+            || Error::InTemplate("Plural-Forms".to_string(), Box::new(Error::UnexpectedToken));
+        let (mut count, mut expression) = (None, None);
+        for assignment in plural_forms.split(';') {
+            match assignment.split_once('=') {
+                Some((key, value)) if key.trim() == "nplurals" => {
+                    count = Some(value.trim().parse::<usize>().map_err(|_| error())?);
+                }
+                Some((key, value)) if key.trim() == "plural" => {
+                    let mut parser = ExpressionParser {
+                        source: value.as_bytes(),
+                        position: 0,
+                        depth: 0,
+                    };
+                    let parsed = parser.ternary().ok_or_else(|| {
+                        if parser.is_too_deep() {
+                            Error::InTemplate(
+                                "Plural-Forms".to_string(),
+                                Box::new(Error::NestingTooDeep(MAX_EXPRESSION_DEPTH)),
+                            )
+                        } else {
+                            error()
+                        }
+                    })?;
+                    parser.blank();
+                    if parser.position != parser.source.len() {
+                        return Err(error());
+                    }
+                    expression = Some(parsed);
+                }
+                _ => {}
+            }
+        }
+
+        match (count, expression) {
+            (Some(count), Some(expression)) => Ok(Self { count, expression }),
+            _ => Err(error()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+#[derive(Debug, Clone)]
+enum Expression {
+    N,
+    Number(u64),
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self, n: u64) -> u64 {
+        match self {
+            Expression::N => n,
+            Expression::Number(number) => *number,
+            Expression::Not(expression) => (expression.evaluate(n) == 0) as u64,
+            Expression::Ternary(condition, then, otherwise) => {
+                if condition.evaluate(n) != 0 {
+                    then.evaluate(n)
+                } else {
+                    otherwise.evaluate(n)
+                }
+            }
+            Expression::Binary(operator, left, right) => {
+                let left = left.evaluate(n);
+                // Short circuiting is irrelevant, since expressions have no side effects
+                let right = right.evaluate(n);
+                match operator {
+                    Operator::Or => (left != 0 || right != 0) as u64,
+                    Operator::And => (left != 0 && right != 0) as u64,
+                    Operator::Equal => (left == right) as u64,
+                    Operator::NotEqual => (left != right) as u64,
+                    Operator::Less => (left < right) as u64,
+                    Operator::LessEqual => (left <= right) as u64,
+                    Operator::Greater => (left > right) as u64,
+                    Operator::GreaterEqual => (left >= right) as u64,
+                    Operator::Add => left.wrapping_add(right),
+                    Operator::Subtract => left.wrapping_sub(right),
+                    Operator::Multiply => left.wrapping_mul(right),
+                    Operator::Divide => left.checked_div(right).unwrap_or_default(),
+                    Operator::Remainder => left.checked_rem(right).unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
+
+/// Maximum nesting of the `Plural-Forms` expressions, which bounds the recursion
+/// of parsing, evaluating and dropping them.
+const MAX_EXPRESSION_DEPTH: usize = 64;
+
+/// Recursive descent parser of the C expressions used by `Plural-Forms`.
+struct ExpressionParser<'s> {
+    source: &'s [u8],
+    position: usize,
+    /// Nesting of the expression being parsed. It is not decreased on failure,
+    /// since every failure aborts the parsing.
+    depth: usize,
+}
+
+impl ExpressionParser<'_> {
+    fn ternary(&mut self) -> Option<Expression> {
+        self.nest()?;
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            self.depth -= 1;
+            return Some(condition);
+        }
+        let then = self.ternary()?;
+        if !self.eat(":") {
+            return None;
+        }
+        let otherwise = self.ternary()?;
+        self.depth -= 1;
+        Some(Expression::Ternary(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn nest(&mut self) -> Option<()> {
+        self.depth += 1;
+        (!self.is_too_deep()).then_some(())
+    }
+
+    fn is_too_deep(&self) -> bool {
+        self.depth > MAX_EXPRESSION_DEPTH
+    }
+
+    /// Parses binary operators by precedence climbing, starting at the precedence level.
+    fn binary(&mut self, level: usize) -> Option<Expression> {
+        const LEVELS: [&[(&str, Operator)]; 6] = [
+            &[("||", Operator::Or)],
+            &[("&&", Operator::And)],
+            &[("==", Operator::Equal), ("!=", Operator::NotEqual)],
+            &[
+                ("<=", Operator::LessEqual),
+                (">=", Operator::GreaterEqual),
+                ("<", Operator::Less),
+                (">", Operator::Greater),
+            ],
+            &[("+", Operator::Add), ("-", Operator::Subtract)],
+            &[
+                ("*", Operator::Multiply),
+                ("/", Operator::Divide),
+                ("%", Operator::Remainder),
+            ],
+        ];
+
+        let Some(operators) = LEVELS.get(level) else {
+            return self.unary();
+        };
+        let mut left = self.binary(level + 1)?;
+        // Every operator nests the expression on its left
+        let depth = self.depth;
+        'operators: loop {
+            for (token, operator) in operators.iter() {
+                if self.eat(token) {
+                    self.nest()?;
+                    let right = self.binary(level + 1)?;
+                    left = Expression::Binary(*operator, Box::new(left), Box::new(right));
+                    continue 'operators;
+                }
+            }
+            self.depth = depth;
+            return Some(left);
+        }
+    }
+
+    fn unary(&mut self) -> Option<Expression> {
+        // `!=` is handled by the equality level
+        if self.peek_token("!") && !self.peek_token("!=") {
+            self.eat("!");
+            self.nest()?;
+            let expression = self.unary()?;
+            self.depth -= 1;
+            return Some(Expression::Not(Box::new(expression)));
+        }
+        if self.eat("(") {
+            let expression = self.ternary()?;
+            return self.eat(")").then_some(expression);
+        }
+        if self.eat("n") {
+            return Some(Expression::N);
+        }
+
+        self.blank();
+        let start = self.position;
+        while self
+            .source
+            .get(self.position)
+            .is_some_and(u8::is_ascii_digit)
+        {
+            self.position += 1;
+        }
+        core::str::from_utf8(&self.source[start..self.position])
+            .ok()?
+            .parse()
+            .ok()
+            .map(Expression::Number)
+    }
+
+    fn peek_token(&mut self, token: &str) -> bool {
+        self.blank();
+        self.source[self.position..].starts_with(token.as_bytes())
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        // `|` and `&` are not supported, hence `||` and `&&` need no special care
+        let is_match = self.peek_token(token)
+            && !(matches!(token, "<" | ">") && self.source.get(self.position + 1) == Some(&b'='));
+        if is_match {
+            self.position += token.len();
+        }
+        is_match
+    }
+
+    fn blank(&mut self) {
+        while self
+            .source
+            .get(self.position)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.position += 1;
+        }
+    }
+}
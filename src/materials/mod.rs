@@ -1,9 +1,34 @@
 mod arguments;
 mod arguments_builder;
+mod compatibility;
 mod dyn_fmt;
+mod extract;
+#[cfg(feature = "fluent")]
+mod fluent;
+mod formatter_registry;
+#[cfg(feature = "gettext")]
+mod gettext;
+#[cfg(feature = "parse_cache")]
+mod parse_cache;
+mod partials;
+mod policy;
+mod regex;
 mod template;
+mod template_registry;
 
 pub use arguments::*;
 pub use arguments_builder::*;
+pub use compatibility::*;
 pub use dyn_fmt::*;
+pub use extract::*;
+#[cfg(feature = "fluent")]
+pub use fluent::*;
+pub use formatter_registry::*;
+#[cfg(feature = "gettext")]
+pub use gettext::*;
+#[cfg(feature = "parse_cache")]
+pub use parse_cache::*;
+pub use partials::*;
+pub use policy::*;
 pub use template::*;
+pub use template_registry::*;
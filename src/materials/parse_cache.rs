@@ -0,0 +1,144 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{Error, Template};
+
+static CACHE: Mutex<Lru> = Mutex::new(Lru {
+    capacity: ParseCache::DEFAULT_CAPACITY,
+    tick: 0,
+    entries: BTreeMap::new(),
+    recency: BTreeMap::new(),
+});
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+struct Lru {
+    capacity: usize,
+    tick: u64,
+    /// The parsed templates with the tick of their last use.
+    entries: BTreeMap<String, (Arc<Template>, u64)>,
+    /// The string templates by the tick of their last use, whose first one is evicted next.
+    recency: BTreeMap<u64, String>,
+}
+
+/// Global bounded LRU cache of parsed string templates, keyed by the string template.
+///
+/// It is used by the macros for templates which are not a literal, e.g. a `String`,
+/// so that repeated dynamic templates are parsed only once.
+/// ```rust
+/// use dfmt::*;
+///
+/// ParseCache::set_capacity(128);
+/// let template = "Hello, {}!".to_string();
+/// dformat!(template, "World").unwrap();
+/// dformat!(template, "World").unwrap();
+/// assert!(ParseCache::stats().hits >= 1);
+/// ```
+pub struct ParseCache;
+
+/// Snapshot of the counters of the [`ParseCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl ParseCache {
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Looks up the parsed template or parses and caches it.
+    /// Templates which fail to parse are not cached.
+    pub fn get_or_parse(template: &str) -> Result<Arc<Template>, Error> {
+        {
+            let mut cache = CACHE.lock().unwrap_or_else(|it| it.into_inner());
+            if let Some(parsed) = cache.touch(template) {
+                HITS.fetch_add(1, Ordering::Relaxed);
+                return Ok(parsed);
+            }
+        }
+
+        MISSES.fetch_add(1, Ordering::Relaxed);
+        // Parsing outside of the lock, so that other threads are not blocked meanwhile
+        let parsed = Arc::new(Template::parse(template)?);
+
+        let mut cache = CACHE.lock().unwrap_or_else(|it| it.into_inner());
+        // Another thread may have cached the template meanwhile
+        if cache.capacity > 0 && cache.touch(template).is_none() {
+            while cache.entries.len() >= cache.capacity {
+                cache.evict_least_recently_used();
+            }
+            let tick = cache.next_tick();
+            cache
+                .entries
+                .insert(template.to_string(), (parsed.clone(), tick));
+            cache.recency.insert(tick, template.to_string());
+        }
+        Ok(parsed)
+    }
+
+    /// Sets the maximal amount of cached templates. `0` disables the cache.
+    pub fn set_capacity(capacity: usize) {
+        let mut cache = CACHE.lock().unwrap_or_else(|it| it.into_inner());
+        cache.capacity = capacity;
+        while cache.entries.len() > capacity {
+            cache.evict_least_recently_used();
+        }
+    }
+
+    /// The maximal amount of cached templates.
+    pub fn capacity() -> usize {
+        CACHE.lock().unwrap_or_else(|it| it.into_inner()).capacity
+    }
+
+    /// Removes all cached templates and resets the counters.
+    pub fn clear() {
+        let mut cache = CACHE.lock().unwrap_or_else(|it| it.into_inner());
+        cache.entries.clear();
+        cache.recency.clear();
+        HITS.store(0, Ordering::Relaxed);
+        MISSES.store(0, Ordering::Relaxed);
+    }
+
+    /// The current counters of the cache.
+    pub fn stats() -> ParseCacheStats {
+        let cache = CACHE.lock().unwrap_or_else(|it| it.into_inner());
+        ParseCacheStats {
+            hits: HITS.load(Ordering::Relaxed),
+            misses: MISSES.load(Ordering::Relaxed),
+            len: cache.entries.len(),
+            capacity: cache.capacity,
+        }
+    }
+}
+
+impl Lru {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Looks up the parsed template and marks it as the most recently used one.
+    fn touch(&mut self, template: &str) -> Option<Arc<Template>> {
+        let tick = self.next_tick();
+        let (parsed, last_use) = self.entries.get_mut(template)?;
+        let previous_use = core::mem::replace(last_use, tick);
+        let parsed = parsed.clone();
+        if let Some(key) = self.recency.remove(&previous_use) {
+            self.recency.insert(tick, key);
+        }
+        Some(parsed)
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some((_, key)) = self.recency.pop_first() {
+            self.entries.remove(&key);
+        }
+    }
+}
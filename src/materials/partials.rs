@@ -0,0 +1,24 @@
+use crate::Template;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, HashMap};
+
+/// Named templates, which are included by `{>name}` placeholders.
+pub trait Partials {
+    fn partial(&self, name: &str) -> Option<&Template>;
+}
+
+impl Partials for BTreeMap<String, Template> {
+    fn partial(&self, name: &str) -> Option<&Template> {
+        self.get(name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Partials for HashMap<String, Template> {
+    fn partial(&self, name: &str) -> Option<&Template> {
+        self.get(name)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    values::{Piece, Precision, Type, Width},
+    Error, Template,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Restrictions for templates from untrusted sources, which are checked by [`Template::parse_with`]
+/// and [`Template::parse_with_policy_and_formatters`].
+/// The default policy allows every template.
+/// ```rust
+/// use dfmt::*;
+///
+/// let policy = TemplatePolicy::default()
+///     .deny(Type::Pointer)
+///     .deny(Type::Debug)
+///     .max_width(64)
+///     .max_precision(16)
+///     .max_placeholders(32)
+///     .max_length(4096)
+///     .dynamic_width_or_precision(false);
+///
+/// assert!(Template::parse_with("Hello {name:>10}!", &policy).is_ok());
+/// assert_eq!(
+///     Template::parse_with("{:p}", &policy).unwrap_err(),
+///     Error::TypeNotAllowed(Type::Pointer)
+/// );
+/// assert_eq!(
+///     Template::parse_with("{:65535}", &policy).unwrap_err(),
+///     Error::WidthTooLarge(65535)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TemplatePolicy {
+    denied_types: Vec<Type>,
+    max_width: Option<u16>,
+    max_precision: Option<u16>,
+    pub(crate) max_placeholders: Option<usize>,
+    max_length: Option<usize>,
+    forbid_dynamic_width_or_precision: bool,
+}
+
+impl TemplatePolicy {
+    /// Builder to deny placeholders of the type, e.g. `Type::Pointer` for `{:p}`.
+    /// Plural, select and `#` placeholders are formatted as `Type::Display`.
+    pub fn deny(mut self, ty: Type) -> Self {
+        if !self.denied_types.contains(&ty) {
+            self.denied_types.push(ty);
+        }
+        self
+    }
+
+    /// Builder to allow placeholders of the type again.
+    pub fn allow(mut self, ty: Type) -> Self {
+        self.denied_types.retain(|denied| denied != &ty);
+        self
+    }
+
+    /// Builder to cap the fixed width, e.g. `{:20}`, which forbids dynamic widths.
+    pub fn max_width(mut self, max_width: u16) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Builder to cap the fixed precision, e.g. `{:.5}`, which forbids dynamic precisions.
+    pub fn max_precision(mut self, max_precision: u16) -> Self {
+        self.max_precision = Some(max_precision);
+        self
+    }
+
+    /// Builder to cap the amount of placeholders, including the ones in plural and select arms.
+    pub fn max_placeholders(mut self, max_placeholders: usize) -> Self {
+        self.max_placeholders = Some(max_placeholders);
+        self
+    }
+
+    /// Builder to cap the length of the template string in bytes.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Builder to allow or forbid dynamic width and precision, e.g. `{:w$.*}`.
+    /// Their values are only known when formatting, hence they are forbidden as well
+    /// by [`TemplatePolicy::max_width`] and [`TemplatePolicy::max_precision`].
+    pub fn dynamic_width_or_precision(mut self, allowed: bool) -> Self {
+        self.forbid_dynamic_width_or_precision = !allowed;
+        self
+    }
+
+    /// Whether the type is allowed.
+    pub fn is_allowed(&self, ty: Type) -> bool {
+        !self.denied_types.contains(&ty)
+    }
+
+    /// Checks the template against the policy, e.g. templates which were not parsed from a string.
+    /// The length is only checked for parsed templates.
+    pub fn check(&self, template: &Template) -> Result<(), Error> {
+        if let Some(source) = &template.source {
+            self.check_length(source)?;
+        }
+
+        let mut placeholders = 0;
+        let mut result = Ok(());
+        Piece::walk(&template.pieces, &mut |piece| {
+            if result.is_err() {
+                return;
+            }
+            if !matches!(
+                piece,
+                Piece::Literal(_) | Piece::BracketOpen | Piece::BracketClose
+            ) {
+                placeholders += 1;
+            }
+            result = self.check_piece(piece);
+        });
+        result?;
+
+        match self.max_placeholders {
+            Some(max_placeholders) if placeholders > max_placeholders => {
+                Err(Error::TooManyPlaceholders(placeholders))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_length(&self, template: &str) -> Result<(), Error> {
+        match self.max_length {
+            Some(max_length) if template.len() > max_length => {
+                Err(Error::TemplateTooLong(template.len()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_piece(&self, piece: &Piece) -> Result<(), Error> {
+        match piece {
+            Piece::Argument { specifier, .. } => {
+                let Some(specifier) = specifier else {
+                    return self.check_type(Type::Display);
+                };
+                self.check_type(specifier.ty)?;
+
+                match &specifier.width {
+                    Width::Fixed(width) => {
+                        if self.max_width.is_some_and(|max_width| *width > max_width) {
+                            return Err(Error::WidthTooLarge(*width));
+                        }
+                    }
+                    Width::Dynamic(key) => {
+                        if self.forbid_dynamic_width_or_precision || self.max_width.is_some() {
+                            return Err(Error::DynamicWidthOrPrecision(key.clone()));
+                        }
+                    }
+                }
+                match &specifier.precision {
+                    Precision::Fixed(precision) => {
+                        if self
+                            .max_precision
+                            .is_some_and(|max_precision| *precision > max_precision)
+                        {
+                            return Err(Error::PrecisionTooLarge(*precision));
+                        }
+                    }
+                    Precision::Dynamic(key) => {
+                        if self.forbid_dynamic_width_or_precision || self.max_precision.is_some() {
+                            return Err(Error::DynamicWidthOrPrecision(key.clone()));
+                        }
+                    }
+                    Precision::Auto => {}
+                }
+                Ok(())
+            }
+            #[cfg(feature = "message_format")]
+            Piece::Choice { .. } | Piece::Count(_) => self.check_type(Type::Display),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_type(&self, ty: Type) -> Result<(), Error> {
+        if self.is_allowed(ty) {
+            Ok(())
+        } else {
+            Err(Error::TypeNotAllowed(ty))
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use core::fmt::Write;
+
+use crate::{
+    values::{Alignment, Piece, Specifier, Type, Width},
+    ArgumentKey, Error, Template,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+impl Template {
+    /// Generates a regular expression, which matches the strings formatted with this template.
+    ///
+    /// Every key gets a named group, `_0` for the index `0` and the name for named keys, where
+    /// characters other than ASCII letters, digits and `_` are replaced by `_`. Names which collide
+    /// with the name of another key get the suffix `_2`, `_3` and so on. Further uses of
+    /// a key are non-capturing, since most regex engines do not allow duplicate group names.
+    /// The literals are escaped and the group patterns are inferred from the placeholder types.
+    /// The syntax is compatible with the `regex` crate, PCRE and Python.
+    ///
+    /// [`Template::extract`] is a built-in matcher, which does not need a regex engine.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("user={name} id={id:#x} took {ms:>5}ms").unwrap();
+    /// assert_eq!(
+    ///     template.to_regex().unwrap(),
+    ///     r"^user=(?P<name>.+?) id=(?P<id>0x[0-9a-f]+?) took  *(?P<ms>.+?)ms$"
+    /// );
+    /// ```
+    pub fn to_regex(&self) -> Result<String, Error> {
+        let mut regex = String::with_capacity(self.capacity * 2);
+        let mut groups = Vec::with_capacity(self.requirements.len());
+
+        regex.push('^');
+        write_pieces(&mut regex, &self.pieces, &mut groups)?;
+        regex.push('$');
+
+        Ok(regex)
+    }
+}
+
+/// The keys which got a named group, alongside the name of their group.
+type Groups<'p> = Vec<(&'p ArgumentKey, String)>;
+
+fn write_pieces<'p>(
+    regex: &mut String,
+    pieces: &'p [Piece],
+    groups: &mut Groups<'p>,
+) -> Result<(), Error> {
+    for piece in pieces {
+        match piece {
+            Piece::Literal(literal) => escape(regex, literal),
+            Piece::BracketOpen => regex.push_str(r"\{"),
+            Piece::BracketClose => regex.push_str(r"\}"),
+            Piece::Partial(name) => return Err(Error::PartialNotFound(name.clone())),
+            Piece::Argument {
+                key,
+                specifier,
+                filters,
+                ..
+            } => write_group(regex, key, specifier.as_ref(), !filters.is_empty(), groups),
+            #[cfg(feature = "message_format")]
+            Piece::Count(key) => write_group(regex, key, None, false, groups),
+            // Alternation of the arms, where keys get a named group in the first arm using them
+            #[cfg(feature = "message_format")]
+            Piece::Choice { arms, .. } => {
+                regex.push_str("(?:");
+                for (index, (_, arm)) in arms.iter().enumerate() {
+                    if index > 0 {
+                        regex.push('|');
+                    }
+                    write_pieces(regex, arm, groups)?;
+                }
+                regex.push(')');
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_group<'p>(
+    regex: &mut String,
+    key: &'p ArgumentKey,
+    specifier: Option<&Specifier>,
+    is_filtered: bool,
+    groups: &mut Groups<'p>,
+) {
+    let (padding_before, padding_after) = padding(specifier);
+    regex.push_str(&padding_before);
+
+    if groups.iter().any(|(group_key, _)| *group_key == key) {
+        regex.push_str("(?:");
+    } else {
+        let name = group_name(key, groups);
+        regex.push_str("(?P<");
+        regex.push_str(&name);
+        regex.push('>');
+        groups.push((key, name));
+    }
+    // Filters may change any value, hence it is matched like a string
+    if is_filtered {
+        regex.push_str(".+?");
+    } else {
+        write_value_pattern(regex, specifier);
+    }
+    regex.push(')');
+
+    regex.push_str(&padding_after);
+}
+
+fn escape(regex: &mut String, literal: &str) {
+    for chr in literal.chars() {
+        if matches!(
+            chr,
+            '\\' | '.'
+                | '+'
+                | '*'
+                | '?'
+                | '('
+                | ')'
+                | '|'
+                | '['
+                | ']'
+                | '{'
+                | '}'
+                | '^'
+                | '$'
+                | '#'
+                | '&'
+                | '-'
+                | '~'
+        ) {
+            regex.push('\\');
+        }
+        regex.push(chr);
+    }
+}
+
+/// Names the group of the key, which is distinct from the names of the other groups.
+fn group_name(key: &ArgumentKey, groups: &Groups<'_>) -> String {
+    let mut name = String::new();
+    match key {
+        ArgumentKey::Index(index) => {
+            let _ = write!(name, "_{}", index);
+        }
+        ArgumentKey::Name(key_name) => {
+            for (index, chr) in key_name.chars().enumerate() {
+                match chr {
+                    'a'..='z' | 'A'..='Z' | '_' => name.push(chr),
+                    '0'..='9' if index > 0 => name.push(chr),
+                    _ => name.push('_'),
+                }
+            }
+        }
+    }
+
+    let is_taken = |name: &str| groups.iter().any(|(_, group_name)| group_name == name);
+    if !is_taken(&name) {
+        return name;
+    }
+    (2..)
+        .map(|suffix| {
+            let mut suffixed = name.clone();
+            let _ = write!(suffixed, "_{}", suffix);
+            suffixed
+        })
+        .find(|suffixed| !is_taken(suffixed))
+        .expect("a suffix is free")
+}
+
+/// Patterns of the padding before and after the value.
+fn padding(specifier: Option<&Specifier>) -> (String, String) {
+    let mut fill = String::new();
+    match specifier {
+        Some(specifier) if !matches!(specifier.width, Width::Fixed(0)) => {
+            escape(&mut fill, specifier.fill_character.encode_utf8(&mut [0; 4]));
+            fill.push('*');
+            match specifier.alignment {
+                Alignment::Left => (String::new(), fill),
+                Alignment::Right => (fill, String::new()),
+                // Numbers are padded with spaces on the left, other values on the right
+                Alignment::Center | Alignment::Auto => (fill.clone(), fill),
+            }
+        }
+        _ => (String::new(), String::new()),
+    }
+}
+
+fn write_value_pattern(regex: &mut String, specifier: Option<&Specifier>) {
+    let Some(specifier) = specifier else {
+        regex.push_str(".+?");
+        return;
+    };
+
+    let (prefix, digits) = match specifier.ty {
+        Type::Display
+        | Type::Debug
+        | Type::WidthOrPrecisionAmount
+        | Type::Bytes
+        | Type::BinaryBytes
+        | Type::Percent
+        | Type::Duration
+        | Type::DateTime
+        | Type::Custom(_) => {
+            regex.push_str(".+?");
+            return;
+        }
+        Type::LowerExp => ("", r"[0-9]+(?:\.[0-9]+)?e-?[0-9]+?"),
+        Type::UpperExp => ("", r"[0-9]+(?:\.[0-9]+)?E-?[0-9]+?"),
+        Type::Binary => ("0b", "[01]+?"),
+        Type::Octal => ("0o", "[0-7]+?"),
+        Type::LowerHex => ("0x", "[0-9a-f]+?"),
+        Type::UpperHex => ("0x", "[0-9A-F]+?"),
+        Type::Pointer => ("0x", "[0-9a-f]+?"),
+    };
+    // Floats keep their minus sign, while the other types write the two's complement
+    if specifier.sign {
+        regex.push_str("[+-]?");
+    } else if matches!(specifier.ty, Type::LowerExp | Type::UpperExp) {
+        regex.push_str("-?");
+    }
+    if specifier.alternate_form || specifier.ty == Type::Pointer {
+        regex.push_str(prefix);
+    }
+    regex.push_str(digits);
+}
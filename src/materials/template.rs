@@ -1,17 +1,32 @@
+use core::ops::Range;
+
 use crate::{
     error::Error,
-    values::{Piece, Precision, Specifier, Type, Width},
-    ArgumentKey, ArgumentTypeRequirements, Arguments, ToArgumentKey,
+    values::{Filter, Piece, Precision, Segment, Specifier, Spelling, Type, Width},
+    ArgumentKey, ArgumentTypeRequirements, ArgumentValue, Arguments, FormatterRegistry, Partials,
+    TemplatePolicy, ToArgumentKey,
 };
 
 #[cfg(not(feature = "std"))]
-use alloc::{string::ToString, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 /// Precompiled version of the string template.
 #[derive(Debug, Clone, Default)]
 pub struct Template {
     pub(crate) pieces: Vec<Piece>,
     pub(crate) requirements: Vec<(ArgumentKey, ArgumentTypeRequirements)>,
+    /// Byte range of every piece within `source`, `None` for pieces added by a builder.
+    pub(crate) spans: Vec<Option<Range<usize>>>,
+    pub(crate) source: Option<String>,
+    /// Estimated length of the formatted output.
+    pub(crate) capacity: usize,
 }
 
 impl Template {
@@ -35,11 +50,113 @@ impl Template {
 
     /// Parses a string template.
     pub fn parse(template: &str) -> Result<Self, Error> {
-        let pieces = Piece::parse(template)?;
+        Self::parse_inner(template, None, None)
+    }
+
+    /// Parses a string template, whose placeholders may use the names of the registered formatters
+    /// as type, e.g. `{amount:money}` or `{size:>10bytes}`.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let formatters = FormatterRegistry::new().formatter("upper", |value, _, output| {
+    ///     match value.downcast_ref::<&str>() {
+    ///         Some(text) => output.write_str(&text.to_uppercase()),
+    ///         None => Err(core::fmt::Error),
+    ///     }
+    /// });
+    ///
+    /// let template = Template::parse_with_formatters("Hello {name:upper}!", &formatters).unwrap();
+    /// let mut arguments = template.arguments();
+    /// arguments.set_formatters(&formatters);
+    /// arguments.add_argument_value("name", ArgumentValue::Custom("upper", &"World")).unwrap();
+    /// assert_eq!(arguments.format().unwrap(), "Hello WORLD!");
+    /// ```
+    pub fn parse_with_formatters(
+        template: &str,
+        formatters: &FormatterRegistry,
+    ) -> Result<Self, Error> {
+        Self::parse_inner(template, Some(formatters), None)
+    }
+
+    fn parse_inner(
+        template: &str,
+        formatters: Option<&FormatterRegistry>,
+        max_placeholders: Option<usize>,
+    ) -> Result<Self, Error> {
+        let (pieces, spans) = Piece::parse(template, formatters, max_placeholders)?;
+
+        Ok(Self::from_pieces(
+            pieces,
+            spans.into_iter().map(Some).collect(),
+            Some(template.to_string()),
+        )
+        .optimize())
+    }
+
+    /// Parses a string template, which has to satisfy the policy.
+    /// The length is checked before parsing and parsing stops at the first placeholder
+    /// beyond the maximum.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let policy = TemplatePolicy::default().deny(Type::Debug).max_placeholders(2);
+    /// assert!(Template::parse_with("{first} {last}", &policy).is_ok());
+    /// assert_eq!(
+    ///     Template::parse_with("{user:?}", &policy).unwrap_err(),
+    ///     Error::TypeNotAllowed(Type::Debug)
+    /// );
+    /// ```
+    pub fn parse_with(template: &str, policy: &TemplatePolicy) -> Result<Self, Error> {
+        Self::parse_checked(template, policy, None)
+    }
+
+    /// Parses a string template like [`Template::parse_with`],
+    /// whose placeholders may use the names of the registered formatters as type.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let formatters = FormatterRegistry::new().formatter("upper", |value, _, output| {
+    ///     match value.downcast_ref::<&str>() {
+    ///         Some(text) => output.write_str(&text.to_uppercase()),
+    ///         None => Err(core::fmt::Error),
+    ///     }
+    /// });
+    /// let policy = TemplatePolicy::default().max_placeholders(1);
+    ///
+    /// assert!(Template::parse_with_policy_and_formatters("{name:upper}", &policy, &formatters).is_ok());
+    /// assert_eq!(
+    ///     Template::parse_with_policy_and_formatters("{a:upper} {b}", &policy, &formatters)
+    ///         .unwrap_err(),
+    ///     Error::TooManyPlaceholders(2)
+    /// );
+    /// ```
+    pub fn parse_with_policy_and_formatters(
+        template: &str,
+        policy: &TemplatePolicy,
+        formatters: &FormatterRegistry,
+    ) -> Result<Self, Error> {
+        Self::parse_checked(template, policy, Some(formatters))
+    }
+
+    fn parse_checked(
+        template: &str,
+        policy: &TemplatePolicy,
+        formatters: Option<&FormatterRegistry>,
+    ) -> Result<Self, Error> {
+        policy.check_length(template)?;
+        let template = Self::parse_inner(template, formatters, policy.max_placeholders)?;
+        policy.check(&template)?;
+        Ok(template)
+    }
 
+    pub(crate) fn from_pieces(
+        pieces: Vec<Piece>,
+        spans: Vec<Option<Range<usize>>>,
+        source: Option<String>,
+    ) -> Self {
         let mut requirements = Vec::with_capacity(pieces.len());
-        pieces.iter().for_each(|piece| {
-            if let Piece::Argument { key, specifier } = piece {
+        Piece::walk(&pieces, &mut |piece| match piece {
+            Piece::Argument { key, specifier, .. } => {
                 if let Some(specifier) = specifier {
                     Template::add_requirement(&mut requirements, key, specifier.ty);
                     if let Precision::Dynamic(precision_key) = &specifier.precision {
@@ -60,12 +177,120 @@ impl Template {
                     Template::add_requirement(&mut requirements, key, Type::Display);
                 }
             }
+            // The count of plurals is parsed from the displayed value
+            #[cfg(feature = "message_format")]
+            Piece::Choice { key, .. } | Piece::Count(key) => {
+                Template::add_requirement(&mut requirements, key, Type::Display)
+            }
+            _ => {}
         });
 
-        Ok(Self {
+        Self {
+            capacity: pieces.iter().map(Piece::estimated_len).sum(),
             pieces,
             requirements,
-        })
+            spans,
+            source,
+        }
+    }
+
+    /// Merges adjacent literals and escaped brackets into a single literal,
+    /// so that formatting has fewer pieces to walk through.
+    /// This is done automatically for parsed templates.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::new()
+    ///     .literal("Hello")
+    ///     .literal(", ")
+    ///     .literal("World!")
+    ///     .optimize();
+    /// assert_eq!(template.segments().count(), 1);
+    /// ```
+    pub fn optimize(mut self) -> Self {
+        let mut pieces: Vec<Piece> = Vec::with_capacity(self.pieces.len());
+        let mut spans: Vec<Option<Range<usize>>> = Vec::with_capacity(self.spans.len());
+
+        for (piece, span) in self.pieces.into_iter().zip(self.spans) {
+            let literal = match &piece {
+                Piece::Literal(literal) => literal.as_str(),
+                Piece::BracketOpen => "{",
+                Piece::BracketClose => "}",
+                _ => {
+                    pieces.push(piece);
+                    spans.push(span);
+                    continue;
+                }
+            };
+
+            match (pieces.last_mut(), spans.last_mut()) {
+                (Some(Piece::Literal(previous)), Some(previous_span)) => {
+                    previous.push_str(literal);
+                    *previous_span = match (previous_span.take(), span) {
+                        (Some(previous_span), Some(span)) if previous_span.end == span.start => {
+                            Some(previous_span.start..span.end)
+                        }
+                        _ => None,
+                    };
+                }
+                _ => {
+                    pieces.push(Piece::Literal(literal.to_string()));
+                    spans.push(span);
+                }
+            }
+        }
+
+        self.pieces = pieces;
+        self.spans = spans;
+        self
+    }
+
+    /// Serializes the template back into a string template.
+    ///
+    /// With [`Spelling::Canonical`] the output is the same as [`ToString::to_string`] and is
+    /// guaranteed to parse back into an equal template. With [`Spelling::Original`] every
+    /// piece which was parsed from a string is written exactly as it was spelled in there,
+    /// while pieces added by the builders fall back to the canonical spelling.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("{: <5}{0:-x}").unwrap();
+    /// assert_eq!(template.to_string_with(Spelling::Canonical), "{:<5}{0:x}");
+    /// assert_eq!(template.to_string_with(Spelling::Original), "{: <5}{0:-x}");
+    /// ```
+    pub fn to_string_with(&self, spelling: Spelling) -> String {
+        match (spelling, &self.source) {
+            (Spelling::Original, Some(source)) => {
+                let mut result = String::with_capacity(source.len());
+                for (piece, span) in self.pieces.iter().zip(&self.spans) {
+                    match span {
+                        Some(span) => result.push_str(&source[span.clone()]),
+                        None => result.push_str(&piece.to_string()),
+                    }
+                }
+                result
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    /// Stable 64-bit fingerprint of the template's content.
+    ///
+    /// Equal templates have equal fingerprints, independent of their original spelling,
+    /// the process or the platform. Therefore it can be persisted and used as a cache key.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let a = Template::parse("Hello, {name: <10}!").unwrap();
+    /// let b = Template::parse("Hello, {name:<10}!").unwrap();
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        // Hashing the canonical spelling keeps the fingerprint independent of the memory layout.
+        core::fmt::Write::write_fmt(&mut hasher, format_args!("{}", self))
+            .expect("hashing never fails");
+        hasher.0
     }
 
     /// Transition into [`Arguments`][$crate::Arguments] for convinience.
@@ -78,9 +303,175 @@ impl Template {
         Ok(self)
     }
 
+    /// Formats the placeholders of the argument into literals and returns a template,
+    /// which only requires the remaining arguments.
+    ///
+    /// Placeholders whose dynamic width or precision is not bound yet are kept.
+    /// The remaining keys keep their index, even if they were implicit before.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("[{tenant:>8}] {message}").unwrap();
+    /// let tenant_template = template
+    ///     .bind("tenant", ArgumentValue::Display(&"acme"))
+    ///     .unwrap();
+    /// assert_eq!(tenant_template.to_string(), "[    acme] {message}");
+    /// ```
+    pub fn bind<K: ToArgumentKey>(&self, key: K, value: ArgumentValue<'_>) -> Result<Self, Error> {
+        self.bind_all(vec![(&key, value)])
+    }
+
+    /// Batch version of [`Template::bind`].
+    pub fn bind_all(
+        &self,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<Self, Error> {
+        let mut arguments = self.arguments();
+        for (key, argument_value) in argument_values {
+            arguments.add_argument_value(key.to_argument_key(), argument_value)?;
+        }
+        arguments.bind()
+    }
+
+    /// Appends another template, as if their strings were concatenated before parsing.
+    ///
+    /// Implicit positional keys (`{}` and `.*`) of the other template continue counting
+    /// after the implicit keys of this template, while explicit keys are kept.
+    /// The `+` operator can be used as well.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let header = Template::parse("{} {}, ").unwrap();
+    /// let body = Template::parse("{} {0}").unwrap();
+    /// assert_eq!(header.clone().append(&body), Template::parse("{} {}, {} {0}").unwrap());
+    /// assert_eq!(header + &body, Template::parse("{} {}, {} {0}").unwrap());
+    /// ```
+    pub fn append(mut self, other: &Template) -> Self {
+        let offset = self.implicit_argument_count();
+
+        let span_offset = match (&mut self.source, &other.source) {
+            (Some(source), Some(other_source)) => {
+                let span_offset = source.len();
+                source.push_str(other_source);
+                Some(span_offset)
+            }
+            (None, Some(other_source)) if self.pieces.is_empty() => {
+                self.source = Some(other_source.clone());
+                Some(0)
+            }
+            _ => None,
+        };
+
+        for (piece, span) in other.pieces.iter().zip(&other.spans) {
+            self.pieces.push(renumber_implicit_keys(piece, offset));
+            self.spans.push(match (span, span_offset) {
+                (Some(span), Some(span_offset)) => {
+                    Some(span.start + span_offset..span.end + span_offset)
+                }
+                _ => None,
+            });
+        }
+
+        Self::from_pieces(self.pieces, self.spans, self.source).optimize()
+    }
+
+    /// Inlines all `{>name}` placeholders recursively with the templates of the partials.
+    ///
+    /// The keys of the partials refer to the same arguments as the keys of this template.
+    /// In contrast to [`Arguments::format_with_partials`], the resulting template
+    /// knows the requirements of the partials, hence it can be used with [`dformat!`][crate::dformat].
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use dfmt::*;
+    ///
+    /// let mut partials = BTreeMap::new();
+    /// partials.insert("footer".to_string(), Template::parse("Regards, {sender}").unwrap());
+    ///
+    /// let template = Template::parse("Hello {name}! {>footer}")
+    ///     .unwrap()
+    ///     .resolve_partials(&partials)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     dformat!(template, name = "Tom", sender = "Ana").unwrap(),
+    ///     "Hello Tom! Regards, Ana"
+    /// );
+    /// ```
+    pub fn resolve_partials(&self, partials: &dyn Partials) -> Result<Self, Error> {
+        let mut pieces = Vec::with_capacity(self.pieces.len());
+        let mut spans = Vec::with_capacity(self.spans.len());
+        for (piece, span) in self.pieces.iter().zip(&self.spans) {
+            if piece.contains_partial() {
+                Self::inline_partial(piece, partials, &mut Vec::new(), &mut pieces)?;
+                spans.resize(pieces.len(), None);
+            } else {
+                pieces.push(piece.clone());
+                spans.push(span.clone());
+            }
+        }
+        Ok(Self::from_pieces(pieces, spans, self.source.clone()).optimize())
+    }
+
+    fn inline_partial<'a>(
+        piece: &'a Piece,
+        partials: &'a dyn Partials,
+        included: &mut Vec<&'a str>,
+        pieces: &mut Vec<Piece>,
+    ) -> Result<(), Error> {
+        match piece {
+            Piece::Partial(name) => {
+                if included.contains(&name.as_str()) {
+                    return Err(Error::PartialCycle(name.clone()));
+                }
+                let partial = partials
+                    .partial(name)
+                    .ok_or_else(|| Error::PartialNotFound(name.clone()))?;
+
+                included.push(name);
+                for piece in &partial.pieces {
+                    Self::inline_partial(piece, partials, included, pieces)?;
+                }
+                included.pop();
+            }
+            // The positional counter of the partial does not continue in the including template
+            Piece::Argument {
+                key,
+                specifier,
+                filters,
+                ..
+            } => pieces.push(Piece::Argument {
+                key: key.clone(),
+                specifier: specifier.clone(),
+                implicit_key: false,
+                implicit_precision: false,
+                filters: filters.clone(),
+            }),
+            #[cfg(feature = "message_format")]
+            Piece::Choice { key, kind, arms } => {
+                let mut inlined_arms = Vec::with_capacity(arms.len());
+                for (selector, arm) in arms {
+                    let mut inlined_arm = Vec::with_capacity(arm.len());
+                    for piece in arm {
+                        Self::inline_partial(piece, partials, included, &mut inlined_arm)?;
+                    }
+                    inlined_arms.push((selector.clone(), inlined_arm));
+                }
+                pieces.push(Piece::Choice {
+                    key: key.clone(),
+                    kind: *kind,
+                    arms: inlined_arms,
+                });
+            }
+            _ => pieces.push(piece.clone()),
+        }
+        Ok(())
+    }
+
     /// Builder to add a literal piece.
     pub fn literal<V: ToString>(mut self, literal: V) -> Self {
-        self.pieces.push(Piece::Literal(literal.to_string()));
+        let piece = Piece::Literal(literal.to_string());
+        self.capacity += piece.estimated_len();
+        self.pieces.push(piece);
+        self.spans.push(None);
         self
     }
 
@@ -102,10 +493,16 @@ impl Template {
                 Type::WidthOrPrecisionAmount,
             );
         }
-        self.pieces.push(Piece::Argument {
+        let piece = Piece::Argument {
             key: argument_key,
             specifier: Some(specifier),
-        });
+            implicit_key: false,
+            implicit_precision: false,
+            filters: Vec::new(),
+        };
+        self.capacity += piece.estimated_len();
+        self.pieces.push(piece);
+        self.spans.push(None);
         self
     }
 
@@ -113,13 +510,147 @@ impl Template {
     pub fn argument<K: ToArgumentKey>(mut self, key: K) -> Self {
         let argument_key = key.to_argument_key();
         Template::add_requirement(&mut self.requirements, &argument_key, Type::Display);
-        self.pieces.push(Piece::Argument {
+        let piece = Piece::Argument {
             key: argument_key,
             specifier: None,
+            implicit_key: false,
+            implicit_precision: false,
+            filters: Vec::new(),
+        };
+        self.capacity += piece.estimated_len();
+        self.pieces.push(piece);
+        self.spans.push(None);
+        self
+    }
+
+    /// Builder to add a filter to the last argument, which is ignored if the last piece is no argument.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::new().argument("name").filter(Filter::Upper);
+    /// assert_eq!(template.to_string(), "{name|upper}");
+    /// ```
+    pub fn filter(mut self, filter: Filter) -> Self {
+        if let Some(Piece::Argument { filters, .. }) = self.pieces.last_mut() {
+            filters.push(filter);
+        }
+        self
+    }
+
+    /// Iterates the pieces of the template.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("Hello, {name:>10}!").unwrap();
+    /// for segment in template.segments() {
+    ///     match segment {
+    ///         Segment::Literal { text, .. } => println!("literal {text:?}"),
+    ///         Segment::Placeholder { key, specifier, span, .. } => {
+    ///             println!("placeholder {key} {specifier:?} at {span:?}")
+    ///         }
+    ///         Segment::Partial { name, .. } => println!("partial {name}"),
+    ///         Segment::Choice { key, .. } => println!("choice {key}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.pieces
+            .iter()
+            .zip(&self.spans)
+            .map(|(piece, span)| match piece {
+                Piece::Literal(text) => Segment::Literal {
+                    text,
+                    span: span.clone(),
+                },
+                Piece::BracketOpen => Segment::Literal {
+                    text: "{",
+                    span: span.clone(),
+                },
+                Piece::BracketClose => Segment::Literal {
+                    text: "}",
+                    span: span.clone(),
+                },
+                Piece::Partial(name) => Segment::Partial {
+                    name,
+                    span: span.clone(),
+                },
+                #[cfg(feature = "message_format")]
+                Piece::Choice { key, .. } => Segment::Choice {
+                    key,
+                    span: span.clone(),
+                },
+                #[cfg(feature = "message_format")]
+                Piece::Count(key) => Segment::Placeholder {
+                    key,
+                    specifier: None,
+                    filters: &[],
+                    implicit: false,
+                    span: span.clone(),
+                },
+                Piece::Argument {
+                    key,
+                    specifier,
+                    implicit_key,
+                    filters,
+                    ..
+                } => Segment::Placeholder {
+                    key,
+                    specifier: specifier.as_ref(),
+                    filters,
+                    implicit: *implicit_key,
+                    span: span.clone(),
+                },
+            })
+    }
+
+    /// Iterates the distinct argument keys in order of their first use,
+    /// alongside the types their values are required to implement.
+    pub fn requirements(&self) -> impl Iterator<Item = (&ArgumentKey, &ArgumentTypeRequirements)> {
+        self.requirements
+            .iter()
+            .map(|(key, requirements)| (key, requirements))
+    }
+
+    /// Iterates the argument keys which are used as dynamic width or precision.
+    pub fn width_or_precision_keys(&self) -> impl Iterator<Item = &ArgumentKey> {
+        self.requirements
+            .iter()
+            .filter(|(_, requirements)| requirements.width_or_precision_amount)
+            .map(|(key, _)| key)
+    }
+
+    /// Amount of positional slots which are assigned implicitly by `{}` and `.*`.
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("{} {:.*} {0} {name}").unwrap();
+    /// assert_eq!(template.implicit_argument_count(), 3);
+    /// ```
+    pub fn implicit_argument_count(&self) -> usize {
+        let mut count = 0;
+        Piece::walk(&self.pieces, &mut |piece| {
+            if let Piece::Argument {
+                implicit_key,
+                implicit_precision,
+                ..
+            } = piece
+            {
+                count += *implicit_key as usize + *implicit_precision as usize;
+            }
         });
+        count
+    }
+
+    /// Builder to add a `{>name}` placeholder, which includes a partial template.
+    pub fn partial<V: ToString>(mut self, name: V) -> Self {
+        let piece = Piece::Partial(name.to_string());
+        self.capacity += piece.estimated_len();
+        self.pieces.push(piece);
+        self.spans.push(None);
         self
     }
 
+    /// Looks up the types the value of the argument key is required to implement.
     pub fn argument_type_requirements(
         &self,
         argument_key: &ArgumentKey,
@@ -148,6 +679,120 @@ impl Template {
     }
 }
 
+/// Shifts the implicit positional keys of the piece by the offset, including the ones within choices.
+fn renumber_implicit_keys(piece: &Piece, offset: usize) -> Piece {
+    let renumber = |key: &ArgumentKey| match key {
+        ArgumentKey::Index(index) => ArgumentKey::Index(index + offset),
+        ArgumentKey::Name(_) => key.clone(),
+    };
+
+    match piece {
+        Piece::Argument {
+            key,
+            specifier,
+            implicit_key,
+            implicit_precision,
+            filters,
+        } => Piece::Argument {
+            key: if *implicit_key {
+                renumber(key)
+            } else {
+                key.clone()
+            },
+            specifier: specifier
+                .as_ref()
+                .map(|specifier| match &specifier.precision {
+                    Precision::Dynamic(precision_key) if *implicit_precision => specifier
+                        .clone()
+                        .precision(Precision::Dynamic(renumber(precision_key))),
+                    _ => specifier.clone(),
+                }),
+            implicit_key: *implicit_key,
+            implicit_precision: *implicit_precision,
+            filters: filters.clone(),
+        },
+        #[cfg(feature = "message_format")]
+        Piece::Choice { key, kind, arms } => Piece::Choice {
+            key: key.clone(),
+            kind: *kind,
+            arms: arms
+                .iter()
+                .map(|(selector, arm)| {
+                    let arm = arm
+                        .iter()
+                        .map(|piece| renumber_implicit_keys(piece, offset))
+                        .collect();
+                    (selector.clone(), arm)
+                })
+                .collect(),
+        },
+        _ => piece.clone(),
+    }
+}
+
+impl core::ops::Add<&Template> for Template {
+    type Output = Template;
+
+    fn add(self, other: &Template) -> Template {
+        self.append(other)
+    }
+}
+
+impl core::ops::Add for Template {
+    type Output = Template;
+
+    fn add(self, other: Template) -> Template {
+        self.append(&other)
+    }
+}
+
+/// Piece of a template whose adjacent literals are merged, like [`Template::optimize`] does.
+#[derive(PartialEq, Eq, Hash)]
+enum NormalizedPiece<'t> {
+    Literal(Cow<'t, str>),
+    Other(&'t Piece),
+}
+
+impl Template {
+    fn normalized_pieces(&self) -> Vec<NormalizedPiece<'_>> {
+        let mut normalized: Vec<NormalizedPiece<'_>> = Vec::with_capacity(self.pieces.len());
+        for piece in &self.pieces {
+            let literal = match piece {
+                Piece::Literal(literal) => literal.as_str(),
+                Piece::BracketOpen => "{",
+                Piece::BracketClose => "}",
+                _ => {
+                    normalized.push(NormalizedPiece::Other(piece));
+                    continue;
+                }
+            };
+            match normalized.last_mut() {
+                _ if literal.is_empty() => {}
+                Some(NormalizedPiece::Literal(previous)) => previous.to_mut().push_str(literal),
+                _ => normalized.push(NormalizedPiece::Literal(Cow::Borrowed(literal))),
+            }
+        }
+        normalized
+    }
+}
+
+/// Templates are equal if they consist of the same pieces, independent of their original spelling
+/// and of how their literals are split, e.g. by the builders.
+impl PartialEq for Template {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized_pieces() == other.normalized_pieces()
+    }
+}
+
+impl Eq for Template {}
+
+impl core::hash::Hash for Template {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.normalized_pieces().hash(state);
+    }
+}
+
+/// Writes the canonical spelling of the template, which parses back into an equal template.
 impl core::fmt::Display for Template {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for piece in &self.pieces {
@@ -173,3 +818,68 @@ impl ToTemplate for &str {
         Template::parse(self)
     }
 }
+
+/// Template of the macros, which borrows precompiled templates and parses string templates,
+/// or takes them from the [`ParseCache`][crate::ParseCache] with the `parse_cache` feature.
+#[doc(hidden)]
+pub enum MacroTemplate<'t> {
+    Borrowed(&'t Template),
+    Parsed(Template),
+    #[cfg(feature = "parse_cache")]
+    Cached(std::sync::Arc<Template>),
+}
+
+impl core::ops::Deref for MacroTemplate<'_> {
+    type Target = Template;
+
+    fn deref(&self) -> &Template {
+        match self {
+            MacroTemplate::Borrowed(template) => template,
+            MacroTemplate::Parsed(template) => template,
+            #[cfg(feature = "parse_cache")]
+            MacroTemplate::Cached(template) => template,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait ToMacroTemplate {
+    fn to_macro_template(&self) -> Result<MacroTemplate<'_>, Error>;
+}
+
+impl ToMacroTemplate for Template {
+    fn to_macro_template(&self) -> Result<MacroTemplate<'_>, Error> {
+        Ok(MacroTemplate::Borrowed(self))
+    }
+}
+
+impl ToMacroTemplate for str {
+    #[cfg(not(feature = "parse_cache"))]
+    fn to_macro_template(&self) -> Result<MacroTemplate<'_>, Error> {
+        Template::parse(self).map(MacroTemplate::Parsed)
+    }
+
+    #[cfg(feature = "parse_cache")]
+    fn to_macro_template(&self) -> Result<MacroTemplate<'_>, Error> {
+        crate::ParseCache::get_or_parse(self).map(MacroTemplate::Cached)
+    }
+}
+
+/// 64-bit FNV-1a, used for the stable [`Template::fingerprint`].
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl core::fmt::Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for byte in s.bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{values::Piece, ArgumentValue, Error, Partials, Template, ToArgumentKey};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use core::cell::OnceCell;
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, sync::OnceLock as OnceCell};
+
+/// Named string templates, which are parsed on first use.
+/// ```rust
+/// use dfmt::*;
+///
+/// let registry = TemplateRegistry::new()
+///     .template("welcome", "Hello {name}!{>footer}")
+///     .template("footer", " Regards, {sender}");
+///
+/// assert_eq!(
+///     registry
+///         .render(
+///             "welcome",
+///             vec![
+///                 (&"name", ArgumentValue::Display(&"Tom")),
+///                 (&"sender", ArgumentValue::Display(&"Ana")),
+///             ]
+///         )
+///         .unwrap(),
+///     "Hello Tom! Regards, Ana"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    entries: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    source: String,
+    /// The template once it is used, or the error of parsing it.
+    template: OnceCell<Result<Template, Error>>,
+    #[cfg(feature = "std")]
+    file: Option<File>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+struct File {
+    path: std::path::PathBuf,
+    /// Modification time and length of the file, which tell that it changed.
+    version: (Option<std::time::SystemTime>, u64),
+    /// Error of the last reload, while the last good template is still served.
+    error: Option<Error>,
+}
+
+impl TemplateRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder to add a string template.
+    pub fn template<N: ToString, S: ToString>(mut self, name: N, source: S) -> Self {
+        self.insert(name, source);
+        self
+    }
+
+    /// Adds or replaces a string template. It is parsed on first use.
+    pub fn insert<N: ToString, S: ToString>(&mut self, name: N, source: S) {
+        self.entries.insert(
+            name.to_string(),
+            Entry {
+                source: source.to_string(),
+                ..Entry::default()
+            },
+        );
+    }
+
+    /// Removes a template and returns its source.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.entries.remove(name).map(|entry| entry.source)
+    }
+
+    /// Whether a template with this name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Iterates the names of all templates.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// The string template as it was added.
+    pub fn source(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(|entry| entry.source.as_str())
+    }
+
+    /// Looks up the template, parsing it if this is its first use.
+    pub fn get(&self, name: &str) -> Result<&Template, Error> {
+        let entry = self
+            .entries
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        entry
+            .template
+            .get_or_init(|| {
+                Template::parse(&entry.source)
+                    .map_err(|error| Error::InTemplate(name.to_string(), Box::new(error)))
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Formats the template with the values, while resolving its `{>name}` placeholders
+    /// against the other templates of the registry.
+    pub fn render(
+        &self,
+        name: &str,
+        argument_values: Vec<(&dyn ToArgumentKey, ArgumentValue<'_>)>,
+    ) -> Result<String, Error> {
+        self.parse_with_partials(name, &mut Vec::new())?;
+
+        let in_template = |error| Error::InTemplate(name.to_string(), Box::new(error));
+        let mut arguments = self.get(name)?.arguments();
+        for (key, argument_value) in argument_values {
+            arguments
+                .add_argument_value(key.to_argument_key(), argument_value)
+                .map_err(in_template)?;
+        }
+        arguments.format_with_partials(self).map_err(in_template)
+    }
+
+    /// Parses the template and its partials up front, which reports their parse errors
+    /// instead of missing partials.
+    fn parse_with_partials<'s>(
+        &'s self,
+        name: &'s str,
+        parsed: &mut Vec<&'s str>,
+    ) -> Result<(), Error> {
+        if parsed.contains(&name) {
+            return Ok(());
+        }
+        let template = self.get(name)?;
+        parsed.push(name);
+
+        let mut partial_names = Vec::new();
+        Piece::walk(&template.pieces, &mut |piece| {
+            if let Piece::Partial(partial_name) = piece {
+                partial_names.push(partial_name.as_str());
+            }
+        });
+        for partial_name in partial_names {
+            // Missing partials are reported with the including template's name while formatting
+            if self.contains(partial_name) {
+                self.parse_with_partials(partial_name, parsed)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl TemplateRegistry {
+    /// Loads all `*.tmpl` files of the directory, named by their file stem.
+    pub fn load_dir<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let mut registry = Self::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|it| it == "tmpl") {
+                registry.load_file(path)?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Loads a single file, named by its file stem.
+    pub fn load_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let name = path.file_stem().and_then(|it| it.to_str()).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid file name")
+        })?;
+        let metadata = std::fs::metadata(path)?;
+        let version = (metadata.modified().ok(), metadata.len());
+        let source = std::fs::read_to_string(path)?;
+        self.entries.insert(
+            name.to_string(),
+            Entry {
+                source,
+                template: OnceCell::new(),
+                file: Some(File {
+                    path: path.to_path_buf(),
+                    version,
+                    error: None,
+                }),
+            },
+        );
+        Ok(())
+    }
+
+    /// Parses all loaded files again, whose modification time or length changed since they were
+    /// parsed last.
+    ///
+    /// If a file cannot be read or parsed, the last good template is kept and the error is
+    /// returned. It is also available through [`TemplateRegistry::error`] until the file is fixed.
+    pub fn refresh(&mut self) -> Vec<Error> {
+        let names = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.file.is_some())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        names
+            .iter()
+            .filter_map(|name| self.reload(name).err())
+            .collect()
+    }
+
+    /// The error of the last failed reload of the file,
+    /// while the last good template is still in use.
+    pub fn error(&self, name: &str) -> Option<&Error> {
+        self.entries
+            .get(name)
+            .and_then(|entry| entry.file.as_ref())
+            .and_then(|file| file.error.as_ref())
+    }
+
+    fn reload(&mut self, name: &str) -> Result<(), Error> {
+        let Some(entry) = self.entries.get_mut(name) else {
+            return Ok(());
+        };
+        let Some(file) = &mut entry.file else {
+            return Ok(());
+        };
+
+        let in_template = |error| Error::InTemplate(name.to_string(), Box::new(error));
+        let io_error = |error| in_template(Error::io(&file.path, &error));
+        let result = std::fs::metadata(&file.path)
+            .map_err(io_error)
+            .and_then(|metadata| {
+                // The length tells apart changes within the resolution of the modification time
+                let version = (metadata.modified().ok(), metadata.len());
+                if file.version == version {
+                    return Ok(None);
+                }
+                let source = std::fs::read_to_string(&file.path).map_err(io_error)?;
+                let template = Template::parse(&source).map_err(in_template)?;
+                Ok(Some((version, source, template)))
+            });
+
+        // The version is only kept on success, hence a broken file is retried
+        // and reported until it is fixed, while the last good source and template stay in use
+        match result {
+            Ok(Some((version, source, template))) => {
+                file.version = version;
+                file.error = None;
+                entry.source = source;
+                entry.template = OnceCell::from(Ok(template));
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(error) => {
+                file.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Templates are parsed on first use as partials as well, while broken ones aren't available.
+impl Partials for TemplateRegistry {
+    fn partial(&self, name: &str) -> Option<&Template> {
+        self.get(name).ok()
+    }
+}
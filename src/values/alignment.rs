@@ -1,7 +1,7 @@
 use core::fmt::Write;
 
 /// Alignment variants of the specifier.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Alignment {
     Left,
     Center,
@@ -4,7 +4,7 @@ use crate::values::Type;
 use alloc::string::{String, ToString};
 
 #[doc(hidden)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TypedArgumentKey {
     pub key: ArgumentKey,
     pub ty: Type,
@@ -17,7 +17,7 @@ impl TypedArgumentKey {
 }
 
 /// The key of an argument.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ArgumentKey {
     Index(usize),
     Name(String),
@@ -1,7 +1,10 @@
 use crate::values::Type;
 
-#[doc(hidden)]
-#[derive(Debug, Clone, Copy, Default)]
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The formatting traits an argument has to implement to satisfy its placeholders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct ArgumentTypeRequirements {
     pub display: bool,
     pub debug: bool,
@@ -13,6 +16,13 @@ pub struct ArgumentTypeRequirements {
     pub pointer: bool,
     pub octal: bool,
     pub width_or_precision_amount: bool,
+    pub bytes: bool,
+    pub binary_bytes: bool,
+    pub percent: bool,
+    pub duration: bool,
+    pub date_time: bool,
+    /// Names of the custom types, sorted and without duplicates, e.g. `money` of `{amount:money}`.
+    pub custom: Vec<&'static str>,
 }
 
 impl ArgumentTypeRequirements {
@@ -28,6 +38,40 @@ impl ArgumentTypeRequirements {
             Type::Debug => self.debug = true,
             Type::Display => self.display = true,
             Type::WidthOrPrecisionAmount => self.width_or_precision_amount = true,
+            Type::Bytes => self.bytes = true,
+            Type::BinaryBytes => self.binary_bytes = true,
+            Type::Percent => self.percent = true,
+            Type::Duration => self.duration = true,
+            Type::DateTime => self.date_time = true,
+            Type::Custom(name) => {
+                if let Err(index) = self.custom.binary_search(&name) {
+                    self.custom.insert(index, name);
+                }
+            }
         };
     }
+
+    /// Iterates the required types.
+    pub fn types(&self) -> impl Iterator<Item = Type> + '_ {
+        [
+            (self.display, Type::Display),
+            (self.debug, Type::Debug),
+            (self.lower_exp, Type::LowerExp),
+            (self.upper_exp, Type::UpperExp),
+            (self.lower_hex, Type::LowerHex),
+            (self.upper_hex, Type::UpperHex),
+            (self.binary, Type::Binary),
+            (self.pointer, Type::Pointer),
+            (self.octal, Type::Octal),
+            (self.width_or_precision_amount, Type::WidthOrPrecisionAmount),
+            (self.bytes, Type::Bytes),
+            (self.binary_bytes, Type::BinaryBytes),
+            (self.percent, Type::Percent),
+            (self.duration, Type::Duration),
+            (self.date_time, Type::DateTime),
+        ]
+        .into_iter()
+        .filter_map(|(required, ty)| required.then_some(ty))
+        .chain(self.custom.iter().map(|name| Type::Custom(name)))
+    }
 }
@@ -1,10 +1,17 @@
-use core::fmt::{Binary, Debug, Display, LowerExp, LowerHex, Octal, Pointer, UpperExp, UpperHex};
+use core::any::Any;
+use core::fmt::{
+    Binary, Debug, Display, Formatter, LowerExp, LowerHex, Octal, Pointer, UpperExp, UpperHex,
+};
+use core::time::Duration;
 
-use crate::values::Type;
+use crate::values::{human_readable, Type};
 
 /// Possible variants of an argument.
 pub enum ArgumentValue<'ct> {
     Display(&'ct dyn Display),
+    /// Number of [`Type::Display`] placeholders, which unlike other displayed values is
+    /// grouped and localized, e.g. `{price:,.2}`.
+    Number(&'ct dyn Number),
     Debug(&'ct dyn Debug),
     Binary(&'ct dyn Binary),
     LowerExp(&'ct dyn LowerExp),
@@ -14,6 +21,34 @@ pub enum ArgumentValue<'ct> {
     Octal(&'ct dyn Octal),
     Pointer(&'ct dyn Pointer),
     WidthOrPrecisionAmount(&'ct dyn WidthOrPrecisionAmount),
+    /// Byte size of [`Type::Bytes`] placeholders, e.g. `{size:>8.1B}`.
+    Bytes(&'ct dyn Quantity),
+    /// Byte size of [`Type::BinaryBytes`] placeholders, e.g. `{size:iB}`.
+    BinaryBytes(&'ct dyn Quantity),
+    /// Ratio of [`Type::Percent`] placeholders, e.g. `{ratio:.1%}`.
+    Percent(&'ct dyn Quantity),
+    /// Duration of [`Type::Duration`] placeholders, e.g. `{elapsed:t}`.
+    Duration(&'ct dyn AsDuration),
+    /// Date or time of [`Type::DateTime`] placeholders, e.g. `{when:%Y-%m-%d}`.
+    /// The `chrono` feature implements it for the dates and times of `chrono`.
+    DateTime(&'ct dyn FormatDateTime),
+    /// Value of the custom type of the name, which is formatted by the
+    /// [`FormatterRegistry`][crate::FormatterRegistry].
+    Custom(&'static str, &'ct dyn Any),
+    /// Value which is only computed if a placeholder uses it, e.g. an expensive display name.
+    /// It counts as [`Display`].
+    /// ```rust
+    /// use dfmt::*;
+    ///
+    /// let template = Template::parse("Hello {name:>6}!").unwrap();
+    /// let mut arguments = template.arguments();
+    /// let name = |f: &mut core::fmt::Formatter| f.pad("Tom");
+    /// let payload = |_: &mut core::fmt::Formatter| -> core::fmt::Result { unreachable!() };
+    /// arguments.add_argument_value("name", ArgumentValue::Lazy(&name)).unwrap();
+    /// arguments.add_argument_value("payload", ArgumentValue::Lazy(&payload)).unwrap();
+    /// assert_eq!(arguments.format().unwrap(), "Hello    Tom!");
+    /// ```
+    Lazy(&'ct dyn Fn(&mut Formatter) -> core::fmt::Result),
 }
 
 impl<'ct> ArgumentValue<'ct> {
@@ -26,7 +61,7 @@ impl<'ct> ArgumentValue<'ct> {
 
     pub fn to_type(&self) -> Type {
         match self {
-            ArgumentValue::Display(_) => Type::Display,
+            ArgumentValue::Display(_) | ArgumentValue::Number(_) => Type::Display,
             ArgumentValue::Debug(_) => Type::Debug,
             ArgumentValue::Binary(_) => Type::Binary,
             ArgumentValue::LowerExp(_) => Type::LowerExp,
@@ -36,6 +71,13 @@ impl<'ct> ArgumentValue<'ct> {
             ArgumentValue::Octal(_) => Type::Octal,
             ArgumentValue::Pointer(_) => Type::Pointer,
             ArgumentValue::WidthOrPrecisionAmount(_) => Type::WidthOrPrecisionAmount,
+            ArgumentValue::Bytes(_) => Type::Bytes,
+            ArgumentValue::BinaryBytes(_) => Type::BinaryBytes,
+            ArgumentValue::Percent(_) => Type::Percent,
+            ArgumentValue::Duration(_) => Type::Duration,
+            ArgumentValue::DateTime(_) => Type::DateTime,
+            ArgumentValue::Custom(name, _) => Type::Custom(name),
+            ArgumentValue::Lazy(_) => Type::Display,
         }
     }
 }
@@ -44,6 +86,7 @@ impl<'ct> core::fmt::Display for ArgumentValue<'ct> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ArgumentValue::Display(display) => (*display).fmt(f),
+            ArgumentValue::Number(number) => (*number).fmt(f),
             ArgumentValue::Debug(debug) => (*debug).fmt(f),
             ArgumentValue::Binary(binary) => (*binary).fmt(f),
             ArgumentValue::LowerExp(lower_exp) => (*lower_exp).fmt(f),
@@ -55,6 +98,15 @@ impl<'ct> core::fmt::Display for ArgumentValue<'ct> {
             ArgumentValue::WidthOrPrecisionAmount(_) => {
                 unreachable!()
             }
+            ArgumentValue::Bytes(bytes) => human_readable::fmt_bytes(f, bytes.to_f64(), false),
+            ArgumentValue::BinaryBytes(bytes) => human_readable::fmt_bytes(f, bytes.to_f64(), true),
+            ArgumentValue::Percent(ratio) => human_readable::fmt_percent(f, ratio.to_f64()),
+            ArgumentValue::Duration(duration) => {
+                human_readable::fmt_duration(f, duration.as_duration())
+            }
+            ArgumentValue::Lazy(lazy) => lazy(f),
+            // Requires the formatter of the registry or the pattern of the specifier
+            ArgumentValue::Custom(..) | ArgumentValue::DateTime(_) => Err(core::fmt::Error),
         }
     }
 }
@@ -95,3 +147,55 @@ impl_width_or_precision_amount!(false, i64);
 impl_width_or_precision_amount!(true, u64);
 impl_width_or_precision_amount!(false, i128);
 impl_width_or_precision_amount!(true, u128);
+
+/// Integer or float of [`ArgumentValue::Number`].
+#[doc(hidden)]
+pub trait Number: Display {}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {}
+        )*
+    };
+}
+
+impl_number!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, f32, f64);
+
+/// Number of the byte size and percent placeholders.
+#[doc(hidden)]
+pub trait Quantity {
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_quantity {
+    ($($ty:ty),*) => {
+        $(
+            impl Quantity for $ty {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_quantity!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, f32, f64);
+
+/// Duration of the duration placeholders.
+#[doc(hidden)]
+pub trait AsDuration {
+    fn as_duration(&self) -> Duration;
+}
+
+impl AsDuration for Duration {
+    fn as_duration(&self) -> Duration {
+        *self
+    }
+}
+
+/// Date or time of the date and time placeholders, which is formatted with a strftime pattern.
+#[doc(hidden)]
+pub trait FormatDateTime {
+    fn fmt_date_time(&self, f: &mut Formatter<'_>, pattern: &str) -> core::fmt::Result;
+}
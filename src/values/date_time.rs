@@ -0,0 +1,32 @@
+use core::fmt::{Display, Formatter};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+use crate::FormatDateTime;
+
+impl FormatDateTime for NaiveDate {
+    fn fmt_date_time(&self, f: &mut Formatter<'_>, pattern: &str) -> core::fmt::Result {
+        self.format(pattern).fmt(f)
+    }
+}
+
+impl FormatDateTime for NaiveTime {
+    fn fmt_date_time(&self, f: &mut Formatter<'_>, pattern: &str) -> core::fmt::Result {
+        self.format(pattern).fmt(f)
+    }
+}
+
+impl FormatDateTime for NaiveDateTime {
+    fn fmt_date_time(&self, f: &mut Formatter<'_>, pattern: &str) -> core::fmt::Result {
+        self.format(pattern).fmt(f)
+    }
+}
+
+impl<Tz: TimeZone> FormatDateTime for DateTime<Tz>
+where
+    Tz::Offset: Display,
+{
+    fn fmt_date_time(&self, f: &mut Formatter<'_>, pattern: &str) -> core::fmt::Result {
+        self.format(pattern).fmt(f)
+    }
+}
@@ -0,0 +1,89 @@
+use core::fmt::Write;
+
+/// Escaping of the formatted argument values, while the literals of the template are kept as is.
+/// Placeholders with the `raw` flag, e.g. `{body:raw}`, are never escaped.
+/// ```rust
+/// use dfmt::*;
+///
+/// let template = Template::parse("<p>{name}</p>{body:raw}").unwrap();
+/// let mut arguments = template.arguments();
+/// arguments.set_escape(Escape::Html);
+/// arguments.add_argument_value("name", ArgumentValue::Display(&"<Tom & Ana>")).unwrap();
+/// arguments.add_argument_value("body", ArgumentValue::Display(&"<br>")).unwrap();
+/// assert_eq!(arguments.format().unwrap(), "<p>&lt;Tom &amp; Ana&gt;</p><br>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Escape {
+    /// Values are written as is.
+    #[default]
+    None,
+    /// Escapes `&`, `<`, `>`, `"` and `'` for HTML text and quoted attribute values.
+    Html,
+    /// Escapes the content of a JSON string, without the surrounding quotes.
+    Json,
+    /// Quotes the value in single quotes for POSIX shells, e.g. `'it'\''s'`.
+    Shell,
+    /// Quotes the value as CSV field, if it contains a comma, quote or line break.
+    Csv,
+}
+
+impl Escape {
+    /// Writes the escaped value to the output.
+    pub(crate) fn write(&self, output: &mut impl Write, value: &str) -> core::fmt::Result {
+        match self {
+            Escape::None => output.write_str(value),
+            Escape::Html => {
+                for chr in value.chars() {
+                    match chr {
+                        '&' => output.write_str("&amp;")?,
+                        '<' => output.write_str("&lt;")?,
+                        '>' => output.write_str("&gt;")?,
+                        '"' => output.write_str("&quot;")?,
+                        '\'' => output.write_str("&#39;")?,
+                        chr => output.write_char(chr)?,
+                    }
+                }
+                Ok(())
+            }
+            Escape::Json => {
+                for chr in value.chars() {
+                    match chr {
+                        '"' => output.write_str("\\\"")?,
+                        '\\' => output.write_str("\\\\")?,
+                        '\n' => output.write_str("\\n")?,
+                        '\r' => output.write_str("\\r")?,
+                        '\t' => output.write_str("\\t")?,
+                        '\u{8}' => output.write_str("\\b")?,
+                        '\u{c}' => output.write_str("\\f")?,
+                        chr if chr.is_control() => write!(output, "\\u{:04x}", chr as u32)?,
+                        chr => output.write_char(chr)?,
+                    }
+                }
+                Ok(())
+            }
+            Escape::Shell => {
+                output.write_char('\'')?;
+                for chr in value.chars() {
+                    match chr {
+                        '\'' => output.write_str("'\\''")?,
+                        chr => output.write_char(chr)?,
+                    }
+                }
+                output.write_char('\'')
+            }
+            Escape::Csv => {
+                if !value.contains([',', '"', '\n', '\r']) {
+                    return output.write_str(value);
+                }
+                output.write_char('"')?;
+                for chr in value.chars() {
+                    match chr {
+                        '"' => output.write_str("\"\"")?,
+                        chr => output.write_char(chr)?,
+                    }
+                }
+                output.write_char('"')
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use core::fmt::Write;
+
+use crate::Error;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Transformation of the formatted text of an argument, which is applied before the padding.
+///
+/// Filters follow the key of a placeholder and are separated by `|`, e.g. `{name|upper}`,
+/// `{slug|lower|trim:>20}` or `{title|truncate(30, "…")}`.
+/// ```rust
+/// use dfmt::*;
+///
+/// let template = Template::parse("[{title|trim|truncate(12, \"…\"):<14}]").unwrap();
+/// let title = "  A very long headline  ";
+/// assert_eq!(
+///     template.arguments().builder().display("title", &title).format().unwrap(),
+///     "[A very long…  ]"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Filter {
+    /// Uppercase, e.g. `HELLO WORLD`.
+    Upper,
+    /// Lowercase, e.g. `hello world`.
+    Lower,
+    /// Uppercases the first letter of every word and lowercases the others, e.g. `Hello World`.
+    Title,
+    /// Removes the leading and trailing whitespace.
+    Trim,
+    /// Cuts the text to the maximum amount of characters, which includes the ellipsis.
+    /// `truncate(30)` cuts without an ellipsis.
+    Truncate { max_len: usize, ellipsis: String },
+}
+
+impl Filter {
+    /// Attempt to parse a single filter, e.g. `lower` or `truncate(30, "…")`.
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let input = input.trim();
+        let (name, arguments) = match input.split_once('(') {
+            Some((name, arguments)) => (
+                name.trim_end(),
+                Some(arguments.strip_suffix(')').ok_or(Error::UnexpectedToken)?),
+            ),
+            None => (input, None),
+        };
+
+        match (name, arguments) {
+            ("upper", None) => Ok(Filter::Upper),
+            ("lower", None) => Ok(Filter::Lower),
+            ("title", None) => Ok(Filter::Title),
+            ("trim", None) => Ok(Filter::Trim),
+            ("truncate", Some(arguments)) => {
+                let (max_len, ellipsis) = match split_unquoted(arguments, b',')?.as_slice() {
+                    [max_len] => (*max_len, None),
+                    [max_len, ellipsis] => (*max_len, Some(parse_string(ellipsis.trim())?)),
+                    _ => return Err(Error::UnexpectedToken),
+                };
+                Ok(Filter::Truncate {
+                    max_len: max_len.trim().parse().map_err(|_| Error::UnexpectedToken)?,
+                    ellipsis: ellipsis.unwrap_or_default(),
+                })
+            }
+            ("upper" | "lower" | "title" | "trim" | "truncate", _) => Err(Error::UnexpectedToken),
+            (name, _) => Err(Error::FilterNotFound(name.to_string())),
+        }
+    }
+
+    /// Parses the filters of a placeholder, which are separated by `|`.
+    pub(crate) fn parse_all(input: &str) -> Result<Vec<Self>, Error> {
+        split_unquoted(input, b'|')?
+            .into_iter()
+            .map(Filter::parse)
+            .collect()
+    }
+
+    /// Returns the index of the `:` or `}` which ends the filters starting at the index,
+    /// skipping over quoted strings.
+    pub(crate) fn end(input: &str, start: usize) -> Result<usize, Error> {
+        let chars = input.as_bytes();
+        let mut index = start;
+        while index < chars.len() {
+            match chars[index] {
+                b':' | b'}' => return Ok(index),
+                b'"' => index = closing_quote(chars, index)?,
+                _ => {}
+            }
+            index += 1;
+        }
+        Err(Error::UnexpectedToken)
+    }
+
+    /// Applies the filter to the text.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Filter::Upper => text.to_uppercase(),
+            Filter::Lower => text.to_lowercase(),
+            Filter::Title => {
+                let mut title = String::with_capacity(text.len());
+                let mut is_word_start = true;
+                for chr in text.chars() {
+                    if is_word_start {
+                        title.extend(chr.to_uppercase());
+                    } else {
+                        title.extend(chr.to_lowercase());
+                    }
+                    is_word_start = !chr.is_alphanumeric() && chr != '\'';
+                }
+                title
+            }
+            Filter::Trim => text.trim().to_string(),
+            Filter::Truncate { max_len, ellipsis } => {
+                if text.chars().count() <= *max_len {
+                    return text.to_string();
+                }
+                let kept = max_len.saturating_sub(ellipsis.chars().count());
+                let mut truncated: String = text.chars().take(kept).collect();
+                truncated.push_str(ellipsis);
+                truncated
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for Filter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Filter::Upper => f.write_str("upper"),
+            Filter::Lower => f.write_str("lower"),
+            Filter::Title => f.write_str("title"),
+            Filter::Trim => f.write_str("trim"),
+            Filter::Truncate { max_len, ellipsis } if ellipsis.is_empty() => {
+                write!(f, "truncate({})", max_len)
+            }
+            Filter::Truncate { max_len, ellipsis } => {
+                write!(f, "truncate({}, \"", max_len)?;
+                for chr in ellipsis.chars() {
+                    if matches!(chr, '"' | '\\') {
+                        f.write_char('\\')?;
+                    }
+                    f.write_char(chr)?;
+                }
+                f.write_char('"')?;
+                f.write_char(')')
+            }
+        }
+    }
+}
+
+/// Splits at the separator outside of quoted strings.
+fn split_unquoted(input: &str, separator: u8) -> Result<Vec<&str>, Error> {
+    let chars = input.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            b'"' => index = closing_quote(chars, index)?,
+            chr if chr == separator => {
+                parts.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    parts.push(&input[start..]);
+    Ok(parts)
+}
+
+/// Returns the index of the quote closing the one at the index. `\"` and `\\` are escapes.
+fn closing_quote(chars: &[u8], open: usize) -> Result<usize, Error> {
+    let mut index = open + 1;
+    while index < chars.len() {
+        match chars[index] {
+            b'\\' => index += 1,
+            b'"' => return Ok(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    Err(Error::UnexpectedToken)
+}
+
+/// Parses a quoted string, e.g. `"…"`.
+fn parse_string(input: &str) -> Result<String, Error> {
+    let content = input
+        .strip_prefix('"')
+        .and_then(|input| input.strip_suffix('"'))
+        .ok_or(Error::UnexpectedToken)?;
+    if closing_quote(input.as_bytes(), 0)? != input.len() - 1 {
+        return Err(Error::UnexpectedToken);
+    }
+
+    let mut string = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(chr) = chars.next() {
+        match chr {
+            '\\' => string.push(chars.next().ok_or(Error::UnexpectedToken)?),
+            chr => string.push(chr),
+        }
+    }
+    Ok(string)
+}
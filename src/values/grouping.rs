@@ -0,0 +1,111 @@
+use core::fmt::Write;
+
+/// Digit grouping of the integer part of a number, e.g. `1,234,567.89` for `{:,.2}`.
+///
+/// Templates spell groups of three separated by `,` or `_`. Other separators and group sizes
+/// are spelled in brackets as the separator, the size of the first group and the size of the
+/// further groups, e.g. `{:[.]}` for `1.234.567` or `{:[,3,2]}` for the Indian `12,34,567`.
+///
+/// Only numbers are grouped, i.e. [`ArgumentValue::Number`][crate::ArgumentValue::Number],
+/// which [`dformat!`][crate::dformat] passes for integers and floats.
+/// ```rust
+/// use dfmt::*;
+///
+/// let template = Template::new()
+///     .specified_argument(0, Specifier::default().grouping(Grouping::indian(',')));
+/// assert_eq!(template.to_string(), "{0:[,3,2]}");
+/// assert_eq!(
+///     template.arguments().builder().number(0, &1234567).format().unwrap(),
+///     "12,34,567"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grouping {
+    pub separator: char,
+    /// Size of the group next to the decimal point.
+    pub primary_size: u8,
+    /// Size of the further groups.
+    pub secondary_size: u8,
+}
+
+impl Grouping {
+    /// Groups of three digits, e.g. `1,234,567`.
+    pub fn new(separator: char) -> Self {
+        Self {
+            separator,
+            primary_size: 3,
+            secondary_size: 3,
+        }
+    }
+
+    /// Indian grouping of three and then two digits, e.g. `12,34,567`.
+    pub fn indian(separator: char) -> Self {
+        Self {
+            separator,
+            primary_size: 3,
+            secondary_size: 2,
+        }
+    }
+
+    /// Builder to specify the group sizes. A size of zero is treated as one.
+    pub fn sizes(mut self, primary_size: u8, secondary_size: u8) -> Self {
+        self.primary_size = primary_size;
+        self.secondary_size = secondary_size;
+        self
+    }
+
+    /// Amount of characters of the digits after grouping.
+    pub(crate) fn grouped_len(&self, digits: usize) -> usize {
+        let (primary_size, secondary_size) = self.normalized_sizes();
+        let separators = if digits <= primary_size {
+            0
+        } else {
+            1 + (digits - primary_size - 1) / secondary_size
+        };
+        digits + separators
+    }
+
+    /// Writes the digits with the separators between the groups.
+    pub(crate) fn write(
+        &self,
+        output: &mut impl core::fmt::Write,
+        digits: &str,
+    ) -> core::fmt::Result {
+        let (primary_size, secondary_size) = self.normalized_sizes();
+        let len = digits.chars().count();
+        for (index, digit) in digits.chars().enumerate() {
+            output.write_char(digit)?;
+            let rest = len - index - 1;
+            if rest == primary_size
+                || (rest > primary_size && (rest - primary_size) % secondary_size == 0)
+            {
+                output.write_char(self.separator)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn normalized_sizes(&self) -> (usize, usize) {
+        (
+            self.primary_size.max(1) as usize,
+            self.secondary_size.max(1) as usize,
+        )
+    }
+}
+
+/// Writes the spelling of the grouping within templates.
+impl core::fmt::Display for Grouping {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if matches!(self.separator, ',' | '_') && *self == Grouping::new(self.separator) {
+            return f.write_char(self.separator);
+        }
+        write!(f, "[{}", self.separator)?;
+        if (self.primary_size, self.secondary_size) != (3, 3) {
+            write!(f, "{}", self.primary_size)?;
+            if self.secondary_size != self.primary_size {
+                write!(f, ",{}", self.secondary_size)?;
+            }
+        }
+        f.write_char(']')
+    }
+}
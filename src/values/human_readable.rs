@@ -0,0 +1,126 @@
+use core::fmt::{Formatter, Write};
+use core::time::Duration;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+const SI_UNITS: [&str; 9] = ["B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
+const IEC_UNITS: [&str; 9] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB"];
+
+/// Units of the durations in nanoseconds, with their compact and verbose names.
+const DURATION_UNITS: [(u128, &str, &str, &str); 7] = [
+    (86_400_000_000_000, "d", "day", "days"),
+    (3_600_000_000_000, "h", "hour", "hours"),
+    (60_000_000_000, "m", "minute", "minutes"),
+    (1_000_000_000, "s", "second", "seconds"),
+    (1_000_000, "ms", "millisecond", "milliseconds"),
+    (1_000, "µs", "microsecond", "microseconds"),
+    (1, "ns", "nanosecond", "nanoseconds"),
+];
+
+/// Writes the byte size in the largest unit below the base, e.g. `1.5 MB` or `1.5 MiB`.
+/// The precision defaults to one fraction digit, while sizes below one unit are written as is.
+pub(crate) fn fmt_bytes(f: &mut Formatter<'_>, bytes: f64, is_binary: bool) -> core::fmt::Result {
+    let (base, units) = if is_binary {
+        (1024.0, IEC_UNITS)
+    } else {
+        (1000.0, SI_UNITS)
+    };
+    let precision = f.precision().unwrap_or(1);
+
+    let mut text = String::new();
+    if !bytes.is_finite() || bytes.abs() < base {
+        write!(text, "{} {}", bytes, units[0])?;
+        return pad(f, text);
+    }
+
+    let mut value = bytes;
+    let mut unit = 0;
+    while value.abs() >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    write!(text, "{:.*}", precision, value)?;
+    // Rounding may reach the base, e.g. `999.95 kB` becomes `1.0 MB` instead of `1000.0 kB`
+    if unit < units.len() - 1 && text.parse::<f64>().is_ok_and(|it| it.abs() >= base) {
+        value /= base;
+        unit += 1;
+        text.clear();
+        write!(text, "{:.*}", precision, value)?;
+    }
+    write!(text, " {}", units[unit])?;
+    pad(f, text)
+}
+
+/// Writes the ratio in percent, e.g. `42%` for `0.421`. The precision defaults to zero.
+pub(crate) fn fmt_percent(f: &mut Formatter<'_>, ratio: f64) -> core::fmt::Result {
+    let mut text = String::new();
+    write!(text, "{:.*}%", f.precision().unwrap_or(0), ratio * 100.0)?;
+    pad(f, text)
+}
+
+/// Writes the duration in its largest units, e.g. `2h 3m` or `2 hours 3 minutes` in the alternate form.
+///
+/// The precision is the amount of consecutive units, which defaults to two.
+/// Smaller units are cut off and units of zero are left out, hence `1h 0m 5s` is written as `1h`.
+pub(crate) fn fmt_duration(f: &mut Formatter<'_>, duration: Duration) -> core::fmt::Result {
+    let is_verbose = f.alternate();
+    let max_units = f.precision().unwrap_or(2).max(1);
+    let mut nanos = duration.as_nanos();
+
+    let mut text = String::new();
+    let Some(largest) = DURATION_UNITS.iter().position(|(unit, ..)| nanos >= *unit) else {
+        text.push_str(if is_verbose { "0 seconds" } else { "0s" });
+        return pad(f, text);
+    };
+    for (unit, short, singular, plural) in DURATION_UNITS.iter().skip(largest).take(max_units) {
+        let amount = nanos / unit;
+        nanos %= unit;
+        if amount == 0 {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        if is_verbose {
+            let name = if amount == 1 { singular } else { plural };
+            write!(text, "{} {}", amount, name)?;
+        } else {
+            write!(text, "{}{}", amount, short)?;
+        }
+    }
+    pad(f, text)
+}
+
+/// Pads the text to the width of the formatter. The values are right aligned by default,
+/// while the zero padding follows the sign. Shows the `+` sign if requested.
+fn pad(f: &mut Formatter<'_>, mut text: String) -> core::fmt::Result {
+    if f.sign_plus() && !text.starts_with('-') {
+        text.insert(0, '+');
+    }
+    let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+
+    if f.sign_aware_zero_pad() {
+        let (sign, rest) = text.split_at(usize::from(text.starts_with(['+', '-'])));
+        f.write_str(sign)?;
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+        return f.write_str(rest);
+    }
+
+    let (before, after) = match f.align() {
+        Some(core::fmt::Alignment::Left) => (0, padding),
+        Some(core::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(core::fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(&text)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use core::fmt::Write;
+
+use crate::{Error, Grouping};
+
+/// Language and region, whose CLDR plural rules select the arms of `{count, plural, ...}` placeholders
+/// and whose number symbols are used for the formatted numbers.
+///
+/// Rules and symbols are built in for the common languages, the others use the English ones.
+/// ```rust
+/// use dfmt::*;
+///
+/// let locale = Locale::parse("pl-PL").unwrap();
+/// assert_eq!(locale.plural_category("1"), Some(PluralCategory::One));
+/// assert_eq!(locale.plural_category("3"), Some(PluralCategory::Few));
+/// assert_eq!(locale.plural_category("5"), Some(PluralCategory::Many));
+/// assert_eq!(locale.plural_category("1.5"), Some(PluralCategory::Other));
+/// assert_eq!(locale.decimal_separator(), ',');
+/// assert_eq!(locale.grouping(), Grouping::new('\u{a0}'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Locale {
+    language: [u8; 3],
+    region: [u8; 3],
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            language: *b"en\0",
+            region: [0; 3],
+        }
+    }
+}
+
+impl Locale {
+    /// Parses a BCP 47 language tag like `en`, `pt-BR` or `zh_Hant_TW`.
+    /// Subtags other than the language, script and region are ignored.
+    pub fn parse(tag: &str) -> Result<Self, Error> {
+        let mut subtags = tag.split(['-', '_']);
+
+        let mut language = [0; 3];
+        match subtags.next() {
+            Some(subtag)
+                if (2..=3).contains(&subtag.len())
+                    && subtag.bytes().all(|it| it.is_ascii_alphabetic()) =>
+            {
+                language[..subtag.len()].copy_from_slice(subtag.as_bytes());
+                language.make_ascii_lowercase();
+            }
+            _ => return Err(Error::UnexpectedToken),
+        }
+
+        let mut region = [0; 3];
+        let mut subtag = subtags.next();
+        if let Some(script) = subtag {
+            if script.len() == 4 && script.bytes().all(|it| it.is_ascii_alphabetic()) {
+                subtag = subtags.next();
+            }
+        }
+        if let Some(subtag) = subtag {
+            if (subtag.len() == 2 && subtag.bytes().all(|it| it.is_ascii_alphabetic()))
+                || (subtag.len() == 3 && subtag.bytes().all(|it| it.is_ascii_digit()))
+            {
+                region[..subtag.len()].copy_from_slice(subtag.as_bytes());
+                region.make_ascii_uppercase();
+            }
+        }
+
+        Ok(Self { language, region })
+    }
+
+    /// The lowercase language subtag, e.g. `pt` for `pt-BR`.
+    pub fn language(&self) -> &str {
+        subtag(&self.language)
+    }
+
+    /// The uppercase region subtag, e.g. `BR` for `pt-BR`.
+    pub fn region(&self) -> Option<&str> {
+        Some(subtag(&self.region)).filter(|region| !region.is_empty())
+    }
+
+    /// Selects the plural category of the decimal number, e.g. `1` or `-2.50`.
+    ///
+    /// Visible fraction digits are respected, hence `1.0` is `Other` in English.
+    /// Returns `None` if the text is not a decimal number.
+    pub fn plural_category(&self, number: &str) -> Option<PluralCategory> {
+        let Operands { i, v, f, t } = Operands::parse(number)?;
+        let is = |value: u64| i == value && f == 0;
+
+        let category = match self.language() {
+            "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "my" | "km" => {
+                PluralCategory::Other
+            }
+            "fr" => match i {
+                0 | 1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+            "pt" if self.region() != Some("PT") => match i {
+                0 | 1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+            "hi" | "bn" | "fa" | "gu" | "kn" | "zu" | "am" => {
+                if i == 0 || is(1) {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "es" | "el" | "hu" | "tr" | "bg" | "nb" | "no" | "sq" | "az" | "ka" | "kk" | "uz"
+            | "ta" | "te" | "ml" | "mr" | "ne" | "eu" => {
+                if is(1) {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "da" => {
+                if is(1) || (t != 0 && i <= 1) {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "ru" | "uk" => {
+                if v != 0 {
+                    PluralCategory::Other
+                } else if i % 10 == 1 && i % 100 != 11 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            "pl" => {
+                if v != 0 {
+                    PluralCategory::Other
+                } else if i == 1 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            "cs" | "sk" => {
+                if v != 0 {
+                    PluralCategory::Many
+                } else if i == 1 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&i) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "hr" | "sr" | "bs" => {
+                if (v == 0 && i % 10 == 1 && i % 100 != 11) || (f % 10 == 1 && f % 100 != 11) {
+                    PluralCategory::One
+                } else if (v == 0 && (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)))
+                    || ((2..=4).contains(&(f % 10)) && !(12..=14).contains(&(f % 100)))
+                {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "lt" => {
+                if f != 0 {
+                    PluralCategory::Many
+                } else if i % 10 == 1 && !(11..=19).contains(&(i % 100)) {
+                    PluralCategory::One
+                } else if (2..=9).contains(&(i % 10)) && !(11..=19).contains(&(i % 100)) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "ro" => {
+                if i == 1 && v == 0 {
+                    PluralCategory::One
+                } else if v != 0 || i == 0 || (1..=19).contains(&(i % 100)) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "he" => {
+                if (i == 1 && v == 0) || (i == 0 && v != 0) {
+                    PluralCategory::One
+                } else if i == 2 && v == 0 {
+                    PluralCategory::Two
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            "ar" => {
+                if is(0) {
+                    PluralCategory::Zero
+                } else if is(1) {
+                    PluralCategory::One
+                } else if is(2) {
+                    PluralCategory::Two
+                } else if f == 0 && (3..=10).contains(&(i % 100)) {
+                    PluralCategory::Few
+                } else if f == 0 && (11..=99).contains(&(i % 100)) {
+                    PluralCategory::Many
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            _ => {
+                if i == 1 && v == 0 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+        };
+        Some(category)
+    }
+
+    /// The decimal separator of the numbers, e.g. `,` for `de`.
+    pub fn decimal_separator(&self) -> char {
+        self.number_symbols().decimal_separator
+    }
+
+    /// The grouping of the numbers, e.g. `.` for `de` or the Indian grouping for `hi`.
+    pub fn grouping(&self) -> Grouping {
+        self.number_symbols().grouping
+    }
+
+    /// The zero of the digit set of the numbers, e.g. `٠` for `ar`.
+    /// The other digits follow the zero, as they do for every Unicode digit set.
+    pub fn zero_digit(&self) -> char {
+        self.number_symbols().zero_digit
+    }
+
+    /// Built-in symbols of the CLDR default numbering system, English for unknown languages.
+    fn number_symbols(&self) -> NumberSymbols {
+        const NBSP: char = '\u{a0}';
+        const NNBSP: char = '\u{202f}';
+
+        let region = self.region();
+        match self.language() {
+            "de" if matches!(region, Some("CH" | "LI")) => NumberSymbols::latin('.', '’'),
+            "it" if region == Some("CH") => NumberSymbols::latin('.', '’'),
+            "de" if region == Some("AT") => NumberSymbols::latin(',', NBSP),
+            "pt" if region == Some("PT") => NumberSymbols::latin(',', NBSP),
+            "es" if matches!(
+                region,
+                Some("MX" | "US" | "GT" | "HN" | "NI" | "PA" | "PE" | "PR" | "SV" | "DO")
+            ) =>
+            {
+                NumberSymbols::latin('.', ',')
+            }
+            "de" | "it" | "es" | "pt" | "nl" | "id" | "da" | "tr" | "el" | "ro" | "hr" | "sr"
+            | "sl" | "bs" | "mk" | "vi" | "is" | "ca" | "gl" | "az" => {
+                NumberSymbols::latin(',', '.')
+            }
+            "fr" => NumberSymbols::latin(',', NNBSP),
+            "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "fi" | "sv" | "nb" | "no" | "nn" | "bg"
+            | "lt" | "lv" | "et" | "hu" | "ka" | "kk" | "uz" | "hy" | "sq" => {
+                NumberSymbols::latin(',', NBSP)
+            }
+            "en" if region == Some("IN") => NumberSymbols::indian('0'),
+            "hi" | "gu" | "kn" | "ml" | "ta" | "te" | "pa" => NumberSymbols::indian('0'),
+            "mr" | "ne" => NumberSymbols::indian('\u{966}'),
+            "bn" => NumberSymbols::indian('\u{9e6}'),
+            "ar" if matches!(region, Some("MA" | "DZ" | "TN" | "LY" | "EH")) => {
+                NumberSymbols::latin(',', '.')
+            }
+            "ar" => NumberSymbols {
+                decimal_separator: '\u{66b}',
+                grouping: Grouping::new('\u{66c}'),
+                zero_digit: '\u{660}',
+            },
+            "fa" => NumberSymbols {
+                decimal_separator: '\u{66b}',
+                grouping: Grouping::new('\u{66c}'),
+                zero_digit: '\u{6f0}',
+            },
+            "my" => NumberSymbols {
+                zero_digit: '\u{1040}',
+                ..NumberSymbols::latin('.', ',')
+            },
+            _ => NumberSymbols::latin('.', ','),
+        }
+    }
+}
+
+impl core::fmt::Display for Locale {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.language())?;
+        if let Some(region) = self.region() {
+            f.write_char('-')?;
+            f.write_str(region)?;
+        }
+        Ok(())
+    }
+}
+
+fn subtag(bytes: &[u8; 3]) -> &str {
+    let len = bytes.iter().position(|it| *it == 0).unwrap_or(bytes.len());
+    // Only ASCII is ever stored
+    core::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
+
+/// Decimal separator, grouping and digit set of the numbers of a locale.
+struct NumberSymbols {
+    decimal_separator: char,
+    grouping: Grouping,
+    zero_digit: char,
+}
+
+impl NumberSymbols {
+    fn latin(decimal_separator: char, group_separator: char) -> Self {
+        Self {
+            decimal_separator,
+            grouping: Grouping::new(group_separator),
+            zero_digit: '0',
+        }
+    }
+
+    fn indian(zero_digit: char) -> Self {
+        Self {
+            decimal_separator: '.',
+            grouping: Grouping::indian(','),
+            zero_digit,
+        }
+    }
+}
+
+/// CLDR plural category, which names the arm of a `{count, plural, ...}` placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// The keyword of the category, e.g. `one`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+
+    #[cfg(feature = "message_format")]
+    pub(crate) fn parse(keyword: &str) -> Option<Self> {
+        match keyword {
+            "zero" => Some(PluralCategory::Zero),
+            "one" => Some(PluralCategory::One),
+            "two" => Some(PluralCategory::Two),
+            "few" => Some(PluralCategory::Few),
+            "many" => Some(PluralCategory::Many),
+            "other" => Some(PluralCategory::Other),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for PluralCategory {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Plural operands of a decimal number as defined by CLDR.
+pub(crate) struct Operands {
+    /// Integer digits.
+    pub i: u64,
+    /// Amount of visible fraction digits.
+    pub v: usize,
+    /// Visible fraction digits.
+    pub f: u64,
+    /// Visible fraction digits without trailing zeros.
+    pub t: u64,
+}
+
+impl Operands {
+    pub fn parse(number: &str) -> Option<Self> {
+        let number = number.trim();
+        let number = number.strip_prefix(['-', '+']).unwrap_or(number);
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty()
+            || !integer.bytes().all(|it| it.is_ascii_digit())
+            || !fraction.bytes().all(|it| it.is_ascii_digit())
+        {
+            return None;
+        }
+
+        Some(Self {
+            i: digits(integer),
+            v: fraction.len(),
+            f: digits(fraction),
+            t: digits(fraction.trim_end_matches('0')),
+        })
+    }
+}
+
+/// Whether both decimal numbers are equal, e.g. `1` and `1.00`.
+#[cfg(feature = "message_format")]
+pub(crate) fn is_same_number(a: &str, b: &str) -> bool {
+    fn normalize(number: &str) -> Option<(bool, &str, &str)> {
+        let number = number.trim();
+        let (negative, number) = match number.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty()
+            || !integer.bytes().all(|it| it.is_ascii_digit())
+            || !fraction.bytes().all(|it| it.is_ascii_digit())
+        {
+            return None;
+        }
+        let integer = integer.trim_start_matches('0');
+        let fraction = fraction.trim_end_matches('0');
+        // Negative zero equals zero
+        Some((
+            negative && !(integer.is_empty() && fraction.is_empty()),
+            integer,
+            fraction,
+        ))
+    }
+
+    match (normalize(a), normalize(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Value of the digits, where values of 19 digits and more keep their last 18 digits and
+/// stay above all values with fewer digits. Hence the rules' remainders remain correct.
+fn digits(digits: &str) -> u64 {
+    const LIMIT: u64 = 1_000_000_000_000_000_000;
+
+    let mut value = 0;
+    let mut is_large = false;
+    for digit in digits.bytes() {
+        value = value * 10 + (digit - b'0') as u64;
+        if value >= LIMIT {
+            is_large = true;
+            value %= LIMIT;
+        }
+    }
+    if is_large {
+        value + LIMIT
+    } else {
+        value
+    }
+}
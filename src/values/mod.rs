@@ -2,9 +2,20 @@ mod alignment;
 mod argument_key;
 mod argument_type_requirements;
 mod argument_value;
+#[cfg(feature = "chrono")]
+mod date_time;
+mod escape;
+mod filter;
+mod grouping;
+mod human_readable;
+#[cfg(feature = "locale")]
+pub(crate) mod locale;
+mod output_limit;
 mod piece;
 mod precision;
+mod segment;
 mod specifier;
+mod spelling;
 mod ty;
 mod width;
 
@@ -12,8 +23,18 @@ pub use alignment::Alignment;
 pub use argument_key::*;
 pub use argument_type_requirements::ArgumentTypeRequirements;
 pub use argument_value::*;
+pub use escape::Escape;
+pub use filter::Filter;
+pub use grouping::Grouping;
+#[cfg(feature = "locale")]
+pub use locale::{Locale, PluralCategory};
+pub use output_limit::OutputLimit;
+#[cfg(feature = "message_format")]
+pub use piece::ChoiceKind;
 pub use piece::Piece;
 pub use precision::Precision;
+pub use segment::Segment;
 pub use specifier::Specifier;
+pub use spelling::Spelling;
 pub use ty::Type;
 pub use width::Width;
@@ -0,0 +1,42 @@
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Maximum length of the formatted output in bytes, after which the formatting stops writing.
+/// ```rust
+/// use dfmt::*;
+///
+/// let name = "World".repeat(1000);
+/// let template = Template::parse("Hello {name}!").unwrap();
+/// let mut arguments = template.arguments();
+/// arguments.add_argument_value("name", ArgumentValue::Display(&name)).unwrap();
+///
+/// arguments.set_output_limit(OutputLimit::Error(64));
+/// assert_eq!(arguments.format().unwrap_err(), Error::OutputLimitExceeded);
+///
+/// arguments.set_output_limit(OutputLimit::Truncate {
+///     max_len: 16,
+///     ellipsis: "...".to_string(),
+/// });
+/// assert_eq!(arguments.format().unwrap(), "Hello WorldWo...");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum OutputLimit {
+    /// The output is not limited.
+    #[default]
+    Unlimited,
+    /// Fails with [`Error::OutputLimitExceeded`][crate::Error::OutputLimitExceeded] once the output exceeds the length.
+    Error(usize),
+    /// Cuts the output, so that it fits into the length together with the ellipsis.
+    /// Escaped values may be cut in the middle.
+    Truncate { max_len: usize, ellipsis: String },
+}
+
+impl OutputLimit {
+    /// The maximum length of the output, if any.
+    pub fn max_len(&self) -> Option<usize> {
+        match self {
+            OutputLimit::Unlimited => None,
+            OutputLimit::Error(max_len) | OutputLimit::Truncate { max_len, .. } => Some(*max_len),
+        }
+    }
+}
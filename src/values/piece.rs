@@ -1,6 +1,11 @@
-use core::fmt::Write;
+use core::{fmt::Write, ops::Range};
 
-use crate::{values::Specifier, ArgumentKey, Error};
+#[cfg(feature = "message_format")]
+use crate::values::{locale::Operands, PluralCategory};
+use crate::{
+    values::{Filter, Precision, Specifier, Type, Width},
+    ArgumentKey, Error, FormatterRegistry,
+};
 
 #[cfg(not(feature = "std"))]
 use alloc::{
@@ -8,7 +13,32 @@ use alloc::{
     vec::Vec,
 };
 
-#[derive(Debug, Clone)]
+/// Maximum amount of choices nested in each other, which bounds the recursion of the parser.
+#[cfg(feature = "message_format")]
+const MAX_CHOICE_DEPTH: usize = 32;
+
+/// State shared by the parsers of nested pieces,
+/// which stops parsing as soon as there are more placeholders than allowed.
+struct ParseContext<'f> {
+    internal_index: usize,
+    formatters: Option<&'f FormatterRegistry>,
+    placeholders: usize,
+    max_placeholders: Option<usize>,
+}
+
+impl ParseContext<'_> {
+    fn add_placeholder(&mut self) -> Result<(), Error> {
+        self.placeholders += 1;
+        match self.max_placeholders {
+            Some(max_placeholders) if self.placeholders > max_placeholders => {
+                Err(Error::TooManyPlaceholders(self.placeholders))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Piece {
     Literal(String),
     BracketOpen,
@@ -16,68 +46,131 @@ pub enum Piece {
     Argument {
         key: ArgumentKey,
         specifier: Option<Specifier>,
+        /// The key was omitted (`{}`) and assigned from the positional counter.
+        implicit_key: bool,
+        /// The precision was given as `.*` and assigned from the positional counter.
+        implicit_precision: bool,
+        /// Transformations of the formatted text, e.g. `{name|upper}`.
+        filters: Vec<Filter>,
+    },
+    /// Placeholder `{>name}`, which includes another template when formatting.
+    Partial(String),
+    /// Placeholder `{key, plural, one {...} other {...}}` or `{key, select, ...}`,
+    /// which formats the arm selected by the value of the argument.
+    #[cfg(feature = "message_format")]
+    Choice {
+        key: ArgumentKey,
+        kind: ChoiceKind,
+        arms: Vec<(String, Vec<Piece>)>,
     },
+    /// `#` within the arms of a plural placeholder, which formats its count.
+    #[cfg(feature = "message_format")]
+    Count(ArgumentKey),
+}
+
+/// Kind of a [`Piece::Choice`], which determines how the arm is selected.
+#[cfg(feature = "message_format")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChoiceKind {
+    /// Selects the arm by an exact match `=N` or the plural category of the number.
+    Plural,
+    /// Selects the arm by the text of the value.
+    Select,
 }
 
 impl Piece {
-    pub fn parse(input: &str) -> Result<Vec<Self>, Error> {
+    /// Parses the pieces of a template alongside the byte range each piece was parsed from.
+    /// The registered names of the formatters are accepted as custom types.
+    /// Parsing fails with [`Error::TooManyPlaceholders`] as soon as the maximum is exceeded.
+    pub fn parse(
+        input: &str,
+        formatters: Option<&FormatterRegistry>,
+        max_placeholders: Option<usize>,
+    ) -> Result<(Vec<Self>, Vec<Range<usize>>), Error> {
+        let mut context = ParseContext {
+            internal_index: 0,
+            formatters,
+            placeholders: 0,
+            max_placeholders,
+        };
         let mut pieces: Vec<Piece> = Vec::with_capacity(10);
+        let mut spans: Vec<Range<usize>> = Vec::with_capacity(10);
 
         let mut cursor = 0;
         let mut current_char = 0;
         let mut bracket = None;
         let mut separator = None;
-        let mut internal_index = 0;
+        let mut filters = None;
 
         let chars = input.as_bytes();
         while current_char < chars.len() {
             let char = chars[current_char];
             match char {
-                b':' if bracket == Some(b'{') => {
-                    if separator.is_some() {
-                        return Err(Error::UnexpectedToken);
-                    }
-
+                // Further colons belong to the specifier, e.g. the fill of `{::<5}` or `%H:%M`
+                b':' if bracket == Some(b'{') && separator.is_none() => {
                     separator = Some(current_char);
                 }
+                // The filters may quote any character, hence they are skipped until their end
+                b'|' if bracket == Some(b'{') && separator.is_none() && filters.is_none() => {
+                    let end = Filter::end(input, current_char + 1)?;
+                    filters = Some(current_char + 1..end);
+                    current_char = end;
+                    continue;
+                }
+                #[cfg(feature = "message_format")]
+                b',' if bracket == Some(b'{') && separator.is_none() && filters.is_none() => {
+                    let (piece, end) =
+                        Piece::parse_choice(input, cursor, current_char, &mut context, None, 1)?;
+                    pieces.push(piece);
+                    spans.push(cursor..end);
+
+                    bracket = None;
+                    cursor = end;
+                    current_char = end;
+                    continue;
+                }
                 b'{' | b'}' => match (bracket, char) {
                     (None, _) => {
                         if cursor < current_char {
                             pieces.push(Piece::Literal(input[cursor..current_char].to_string()));
+                            spans.push(cursor..current_char);
                         }
                         bracket = Some(char);
                         cursor = current_char;
                     }
                     (Some(b'{'), b'}') => {
-                        let specifier = if let Some(seperator_index) = separator {
-                            Some(Specifier::parse(
-                                &input[seperator_index + 1..current_char],
-                                &mut internal_index,
-                            )?)
-                        } else {
-                            None
+                        let (name_start, name_end) = match (&filters, separator) {
+                            (Some(filters), _) => (cursor + 1, filters.start - 1),
+                            (None, None) => (cursor + 1, current_char),
+                            (None, Some(seperator_index)) => (cursor + 1, seperator_index),
                         };
 
-                        let key = {
-                            let (name_start, name_end) = match separator {
-                                None => (cursor + 1, current_char),
-                                Some(seperator_index) => (cursor + 1, seperator_index),
-                            };
-
-                            if name_start == name_end {
-                                internal_index += 1;
-                                ArgumentKey::Index(internal_index - 1)
-                            } else {
-                                match input[name_start..name_end].parse::<usize>() {
-                                    Ok(arg_index) => ArgumentKey::Index(arg_index),
-                                    Err(_) => {
-                                        ArgumentKey::Name(input[name_start..name_end].to_string())
-                                    }
-                                }
+                        if let Some(b'>') = chars.get(name_start) {
+                            if separator.is_some()
+                                || filters.is_some()
+                                || name_start + 1 == name_end
+                            {
+                                return Err(Error::UnexpectedToken);
                             }
-                        };
+                            context.add_placeholder()?;
+                            pieces
+                                .push(Piece::Partial(input[name_start + 1..name_end].to_string()));
+                            spans.push(cursor..current_char + 1);
+
+                            bracket = None;
+                            cursor = current_char + 1;
+                            current_char += 1;
+                            continue;
+                        }
 
-                        pieces.push(Piece::Argument { key, specifier });
+                        pieces.push(Piece::parse_argument(
+                            input,
+                            name_start..name_end,
+                            filters.take(),
+                            separator.map(|seperator_index| seperator_index + 1..current_char),
+                            &mut context,
+                        )?);
+                        spans.push(cursor..current_char + 1);
 
                         separator = None;
                         bracket = None;
@@ -85,11 +178,13 @@ impl Piece {
                     }
                     (Some(b'{'), b'{') => {
                         pieces.push(Piece::BracketOpen);
+                        spans.push(cursor..current_char + 1);
                         bracket = None;
                         cursor = current_char + 1;
                     }
                     (Some(b'}'), b'}') => {
                         pieces.push(Piece::BracketClose);
+                        spans.push(cursor..current_char + 1);
                         bracket = None;
                         cursor = current_char + 1;
                     }
@@ -104,28 +199,383 @@ impl Piece {
 
         if cursor < current_char {
             pieces.push(Piece::Literal(input[cursor..current_char].to_string()));
+            spans.push(cursor..current_char);
         }
 
         if bracket.is_some() {
             Err(Error::UnexpectedToken)
         } else {
-            Ok(pieces)
+            Ok((pieces, spans))
+        }
+    }
+
+    /// Parses the placeholder of an argument from the ranges of its name and specifier.
+    fn parse_argument(
+        input: &str,
+        name: Range<usize>,
+        filters: Option<Range<usize>>,
+        specifier: Option<Range<usize>>,
+        context: &mut ParseContext<'_>,
+    ) -> Result<Self, Error> {
+        context.add_placeholder()?;
+        let filters = match filters {
+            Some(filters) => Filter::parse_all(&input[filters])?,
+            None => Vec::new(),
+        };
+
+        // The implicit precision `.*` takes its positional slot before the value
+        let internal_index_before_specifier = context.internal_index;
+        let specifier = match specifier {
+            Some(specifier) => Some(Specifier::parse_inner(
+                &input[specifier],
+                &mut context.internal_index,
+                context.formatters,
+            )?),
+            None => None,
+        };
+        let implicit_precision = context.internal_index != internal_index_before_specifier;
+
+        let implicit_key = name.is_empty();
+        let key = if implicit_key {
+            context.internal_index += 1;
+            ArgumentKey::Index(context.internal_index - 1)
+        } else {
+            Piece::parse_key(&input[name])
+        };
+
+        Ok(Piece::Argument {
+            key,
+            specifier,
+            implicit_key,
+            implicit_precision,
+            filters,
+        })
+    }
+
+    fn parse_key(name: &str) -> ArgumentKey {
+        match name.parse::<usize>() {
+            Ok(arg_index) => ArgumentKey::Index(arg_index),
+            Err(_) => ArgumentKey::Name(name.to_string()),
+        }
+    }
+
+    /// Parses `{key, plural|select, selector {arm} ...}` starting at its opening bracket,
+    /// returning the piece and the index after its closing bracket.
+    /// The depth counts the choices this one is nested in, including itself.
+    #[cfg(feature = "message_format")]
+    fn parse_choice(
+        input: &str,
+        open: usize,
+        comma: usize,
+        context: &mut ParseContext<'_>,
+        count: Option<&ArgumentKey>,
+        depth: usize,
+    ) -> Result<(Self, usize), Error> {
+        if depth > MAX_CHOICE_DEPTH {
+            return Err(Error::NestingTooDeep(MAX_CHOICE_DEPTH));
+        }
+        context.add_placeholder()?;
+
+        let chars = input.as_bytes();
+        let name = input[open + 1..comma].trim();
+        if name.is_empty() {
+            return Err(Error::UnexpectedToken);
+        }
+        let key = Piece::parse_key(name);
+
+        let kind_end = comma + 1 + input[comma + 1..].find(',').ok_or(Error::UnexpectedToken)?;
+        let kind = match input[comma + 1..kind_end].trim() {
+            "plural" => ChoiceKind::Plural,
+            "select" => ChoiceKind::Select,
+            _ => return Err(Error::UnexpectedToken),
+        };
+        let count = match kind {
+            ChoiceKind::Plural => Some(&key),
+            ChoiceKind::Select => count,
+        };
+
+        let mut arms: Vec<(String, Vec<Piece>)> = Vec::new();
+        let mut position = kind_end + 1;
+        loop {
+            while chars.get(position).is_some_and(u8::is_ascii_whitespace) {
+                position += 1;
+            }
+            match chars.get(position) {
+                None => return Err(Error::UnexpectedToken),
+                Some(b'}') => break,
+                Some(_) => {}
+            }
+
+            let selector_start = position;
+            while chars
+                .get(position)
+                .is_some_and(|it| !it.is_ascii_whitespace() && !matches!(it, b'{' | b'}'))
+            {
+                position += 1;
+            }
+            let selector = &input[selector_start..position];
+            while chars.get(position).is_some_and(u8::is_ascii_whitespace) {
+                position += 1;
+            }
+
+            let is_valid_selector = match kind {
+                ChoiceKind::Plural => match selector.strip_prefix('=') {
+                    Some(number) => Operands::parse(number).is_some(),
+                    None => PluralCategory::parse(selector).is_some(),
+                },
+                ChoiceKind::Select => !selector.is_empty(),
+            };
+            if !is_valid_selector
+                || chars.get(position) != Some(&b'{')
+                || arms.iter().any(|(other, _)| other == selector)
+            {
+                return Err(Error::UnexpectedToken);
+            }
+
+            let (arm, end) = Piece::parse_arm(input, position + 1, context, count, depth)?;
+            arms.push((selector.to_string(), arm));
+            position = end + 1;
+        }
+
+        if !arms.iter().any(|(selector, _)| selector == "other") {
+            return Err(Error::UnexpectedToken);
+        }
+
+        Ok((Piece::Choice { key, kind, arms }, position + 1))
+    }
+
+    /// Parses the pieces of an arm starting after its opening bracket,
+    /// returning the pieces and the index of its closing bracket.
+    ///
+    /// Within arms `{{` escapes an opening bracket, while a closing bracket always ends the arm.
+    #[cfg(feature = "message_format")]
+    fn parse_arm(
+        input: &str,
+        start: usize,
+        context: &mut ParseContext<'_>,
+        count: Option<&ArgumentKey>,
+        depth: usize,
+    ) -> Result<(Vec<Self>, usize), Error> {
+        let chars = input.as_bytes();
+        let mut pieces = Vec::new();
+        let mut literal_start = start;
+        let mut position = start;
+
+        let flush = |pieces: &mut Vec<Piece>, literal_start: usize, position: usize| {
+            if literal_start < position {
+                pieces.push(Piece::Literal(input[literal_start..position].to_string()));
+            }
+        };
+
+        while position < chars.len() {
+            match (chars[position], count) {
+                (b'}', _) => {
+                    flush(&mut pieces, literal_start, position);
+                    return Ok((pieces, position));
+                }
+                (b'#', Some(count)) => {
+                    flush(&mut pieces, literal_start, position);
+                    context.add_placeholder()?;
+                    pieces.push(Piece::Count(count.clone()));
+                    position += 1;
+                    literal_start = position;
+                }
+                (b'{', _) if chars.get(position + 1) == Some(&b'{') => {
+                    flush(&mut pieces, literal_start, position);
+                    pieces.push(Piece::BracketOpen);
+                    position += 2;
+                    literal_start = position;
+                }
+                (b'{', _) => {
+                    flush(&mut pieces, literal_start, position);
+                    let name_end = position
+                        + 1
+                        + chars[position + 1..]
+                            .iter()
+                            .position(|it| matches!(it, b',' | b'|' | b':' | b'{' | b'}'))
+                            .ok_or(Error::UnexpectedToken)?;
+                    match chars[name_end] {
+                        b',' => {
+                            let (piece, end) = Piece::parse_choice(
+                                input,
+                                position,
+                                name_end,
+                                context,
+                                count,
+                                depth + 1,
+                            )?;
+                            pieces.push(piece);
+                            position = end;
+                        }
+                        b'{' => return Err(Error::UnexpectedToken),
+                        _ => {
+                            let (filters, specifier_start) = if chars[name_end] == b'|' {
+                                let end = Filter::end(input, name_end + 1)?;
+                                (Some(name_end + 1..end), end)
+                            } else {
+                                (None, name_end)
+                            };
+                            let close = specifier_start
+                                + chars[specifier_start..]
+                                    .iter()
+                                    .position(|it| *it == b'}')
+                                    .ok_or(Error::UnexpectedToken)?;
+                            let specifier = (chars[specifier_start] == b':')
+                                .then_some(specifier_start + 1..close);
+                            if chars.get(position + 1) == Some(&b'>') {
+                                if specifier.is_some()
+                                    || filters.is_some()
+                                    || position + 2 == name_end
+                                {
+                                    return Err(Error::UnexpectedToken);
+                                }
+                                context.add_placeholder()?;
+                                pieces.push(Piece::Partial(
+                                    input[position + 2..name_end].to_string(),
+                                ));
+                            } else {
+                                pieces.push(Piece::parse_argument(
+                                    input,
+                                    position + 1..name_end,
+                                    filters,
+                                    specifier,
+                                    context,
+                                )?);
+                            }
+                            position = close + 1;
+                        }
+                    }
+                    literal_start = position;
+                }
+                _ => position += 1,
+            }
+        }
+
+        Err(Error::UnexpectedToken)
+    }
+}
+
+impl Piece {
+    /// Calls the function for every piece, including the pieces within the arms of choices.
+    pub fn walk<'p>(pieces: &'p [Piece], function: &mut impl FnMut(&'p Piece)) {
+        for piece in pieces {
+            function(piece);
+            #[cfg(feature = "message_format")]
+            if let Piece::Choice { arms, .. } = piece {
+                for (_, arm) in arms {
+                    Piece::walk(arm, function);
+                }
+            }
+        }
+    }
+
+    /// Whether the piece is or contains a partial.
+    pub fn contains_partial(&self) -> bool {
+        let mut contains_partial = false;
+        Piece::walk(core::slice::from_ref(self), &mut |piece| {
+            contains_partial |= matches!(piece, Piece::Partial(_));
+        });
+        contains_partial
+    }
+
+    /// Estimated amount of bytes this piece occupies in the formatted output.
+    pub fn estimated_len(&self) -> usize {
+        match self {
+            Piece::Literal(literal) => literal.len(),
+            Piece::BracketOpen | Piece::BracketClose => 1,
+            Piece::Partial(_) => 64,
+            #[cfg(feature = "message_format")]
+            Piece::Choice { arms, .. } => arms
+                .iter()
+                .map(|(_, pieces)| pieces.iter().map(Piece::estimated_len).sum())
+                .max()
+                .unwrap_or_default(),
+            #[cfg(feature = "message_format")]
+            Piece::Count(_) => 8,
+            Piece::Argument {
+                specifier: None, ..
+            } => 16,
+            Piece::Argument {
+                specifier: Some(specifier),
+                ..
+            } => {
+                let mut len = match specifier.ty {
+                    Type::Binary => 32,
+                    Type::Octal => 22,
+                    Type::LowerExp | Type::UpperExp => 24,
+                    Type::Pointer => 18,
+                    _ => 16,
+                };
+                if specifier.alternate_form {
+                    len += 2;
+                }
+                if let Precision::Fixed(precision) = specifier.precision {
+                    len += precision as usize;
+                }
+                match specifier.width {
+                    // The fill character may take up to 4 bytes
+                    Width::Fixed(width) => {
+                        len.max(width as usize * specifier.fill_character.len_utf8())
+                    }
+                    Width::Dynamic(_) => len,
+                }
+            }
         }
     }
 }
 
+/// Writes the canonical spelling of the piece, which parses back into the same piece.
 impl core::fmt::Display for Piece {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Piece::Literal(literal) => f.write_str(literal),
+            Piece::Literal(literal) => {
+                for chr in literal.chars() {
+                    match chr {
+                        '{' => f.write_str("{{")?,
+                        '}' => f.write_str("}}")?,
+                        _ => f.write_char(chr)?,
+                    }
+                }
+                Ok(())
+            }
             Piece::BracketOpen => f.write_str("{{"),
             Piece::BracketClose => f.write_str("}}"),
-            Piece::Argument { key, specifier } => {
+            Piece::Partial(name) => write!(f, "{{>{}}}", name),
+            #[cfg(feature = "message_format")]
+            Piece::Choice { key, kind, arms } => {
+                let kind = match kind {
+                    ChoiceKind::Plural => "plural",
+                    ChoiceKind::Select => "select",
+                };
+                write!(f, "{{{}, {},", key, kind)?;
+                for (selector, pieces) in arms {
+                    write!(f, " {} {{", selector)?;
+                    for piece in pieces {
+                        write!(f, "{}", piece)?;
+                    }
+                    f.write_char('}')?;
+                }
+                f.write_char('}')
+            }
+            #[cfg(feature = "message_format")]
+            Piece::Count(_) => f.write_char('#'),
+            Piece::Argument {
+                key,
+                specifier,
+                implicit_key,
+                implicit_precision,
+                filters,
+            } => {
                 f.write_char('{')?;
-                write!(f, "{}", key)?;
+                if !implicit_key {
+                    write!(f, "{}", key)?;
+                }
+                for filter in filters {
+                    write!(f, "|{}", filter)?;
+                }
                 if let Some(specifier) = specifier {
                     f.write_char(':')?;
-                    write!(f, "{}", specifier)?;
+                    specifier.write_canonical(f, *implicit_precision)?;
                 }
                 f.write_char('}')
             }
@@ -0,0 +1,38 @@
+use core::ops::Range;
+
+use crate::{ArgumentKey, Filter, Specifier};
+
+/// Read-only view on a piece of a [`Template`][crate::Template].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<'t> {
+    /// Text which is written as is. Escaped brackets are unescaped.
+    Literal {
+        text: &'t str,
+        /// Byte range within the parsed string, `None` if added by a builder.
+        span: Option<Range<usize>>,
+    },
+    /// Argument which is formatted with the given specifier.
+    Placeholder {
+        key: &'t ArgumentKey,
+        specifier: Option<&'t Specifier>,
+        /// Filters which are applied to the formatted text, e.g. `{name|upper}`.
+        filters: &'t [Filter],
+        /// The key was omitted (`{}`) and assigned from the positional counter.
+        implicit: bool,
+        /// Byte range within the parsed string, `None` if added by a builder.
+        span: Option<Range<usize>>,
+    },
+    /// Placeholder `{>name}`, which includes another template when formatting.
+    Partial {
+        name: &'t str,
+        /// Byte range within the parsed string, `None` if added by a builder.
+        span: Option<Range<usize>>,
+    },
+    /// Placeholder `{key, plural, ...}` or `{key, select, ...}`, which formats one of its arms.
+    /// Only parsed with the `message_format` feature.
+    Choice {
+        key: &'t ArgumentKey,
+        /// Byte range within the parsed string, `None` if added by a builder.
+        span: Option<Range<usize>>,
+    },
+}
@@ -1,12 +1,12 @@
 use core::fmt::Write;
 
 use crate::{
-    values::{Alignment, Precision, Type, Width},
-    ArgumentKey, Error,
+    values::{Alignment, Grouping, Precision, Type, Width},
+    ArgumentKey, Error, FormatterRegistry,
 };
 
 #[cfg(not(feature = "std"))]
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 
 /// Argument formatting specifier.
 /// ```rust
@@ -20,9 +20,13 @@ use alloc::string::ToString;
 ///     .fill_character('*')
 ///     .alignment(Alignment::Center)
 ///     .width(Width::Fixed(20))
-///     .precision(Precision::Auto);
+///     .grouping(Grouping::new(','))
+///     .precision(Precision::Auto)
+///     .raw(false);
+///
+/// Specifier::default().date_time_format("%Y-%m-%d");
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Specifier {
     pub ty: Type,
     pub alternate_form: bool,
@@ -31,15 +35,45 @@ pub struct Specifier {
     pub sign: bool,
     pub pad_zero: bool,
     pub width: Width,
+    /// Digit grouping of numbers formatted as [`Type::Display`].
+    pub grouping: Option<Grouping>,
     pub precision: Precision,
+    /// The strftime pattern of [`Type::DateTime`], e.g. `%Y-%m-%d %H:%M`.
+    pub date_time_format: Option<String>,
+    /// Opts out of the [`Escape`][crate::Escape] of the arguments.
+    pub raw: bool,
+    /// Minimum amount of fraction digits of numbers, down to which the trailing zeros of the fixed
+    /// precision are trimmed. Only Fluent's `NUMBER()` sets it, as it isn't part of the syntax.
+    pub(crate) min_fraction_digits: Option<u16>,
+    /// Minimum amount of integer digits of numbers, which are padded with zeros.
+    /// Only Fluent's `NUMBER()` sets it, as it isn't part of the syntax.
+    pub(crate) min_integer_digits: Option<u16>,
 }
 
 impl Specifier {
     /// Attempt to parse a specifier.
     pub fn parse(input: &str, internal_index: &mut usize) -> Result<Self, Error> {
+        Self::parse_inner(input, internal_index, None)
+    }
+
+    /// Attempt to parse a specifier, whose type may be the name of a registered formatter,
+    /// e.g. `>10bytes`.
+    pub fn parse_with_formatters(
+        input: &str,
+        internal_index: &mut usize,
+        formatters: &FormatterRegistry,
+    ) -> Result<Self, Error> {
+        Self::parse_inner(input, internal_index, Some(formatters))
+    }
+
+    pub(crate) fn parse_inner(
+        input: &str,
+        internal_index: &mut usize,
+        formatters: Option<&FormatterRegistry>,
+    ) -> Result<Self, Error> {
         // Parsing the specifier first, because if it contains a precision .*
         // then the index of the precision argument is before the omitted argument index
-        // Format: [argument_index][name][':' [fill][align][sign]['#']['0'][width]['.' precision][type]]
+        // Format: [argument_index][name][':' [fill][align][sign]['#']['0'][width][grouping]['.' precision['..' precision]][type]['raw']]
         let mut current_specifier_index = 0;
         let chars = input.as_bytes();
 
@@ -50,8 +84,8 @@ impl Specifier {
             return Ok(specifier);
         }
 
-        if let Some(fill_character) = parse_fill_character(&chars[current_specifier_index..]) {
-            current_specifier_index += 1;
+        if let Some(fill_character) = parse_fill_character(&input[current_specifier_index..]) {
+            current_specifier_index += fill_character.len_utf8();
             specifier.fill_character = fill_character;
         }
 
@@ -84,6 +118,11 @@ impl Specifier {
             specifier.width = width;
         }
 
+        if let Some((grouping, incr_index)) = parse_grouping(&input[current_specifier_index..]) {
+            current_specifier_index += incr_index;
+            specifier.grouping = Some(grouping);
+        }
+
         if let Some((precision, incr_index)) = parse_precision(
             &chars[current_specifier_index..],
             &input[current_specifier_index..],
@@ -93,12 +132,30 @@ impl Specifier {
             specifier.precision = precision;
         }
 
-        if let Some(ty) = parse_ty(&chars[current_specifier_index..]) {
-            current_specifier_index += 1;
+        if let Some((name, incr_index)) = formatters
+            .and_then(|formatters| parse_custom_ty(&input[current_specifier_index..], formatters))
+        {
+            current_specifier_index += incr_index;
+            specifier.ty = Type::Custom(name);
+        } else if let Some((pattern, incr_index)) =
+            parse_date_time_format(&input[current_specifier_index..])?
+        {
+            current_specifier_index += incr_index;
+            specifier.ty = Type::DateTime;
+            specifier.date_time_format = Some(pattern.to_string());
+        } else if let Some((ty, incr_index)) = parse_ty(&chars[current_specifier_index..]) {
+            current_specifier_index += incr_index;
             specifier.ty = ty;
         }
 
-        if current_specifier_index < input.len() {
+        if input[current_specifier_index..].starts_with("raw") {
+            current_specifier_index += 3;
+            specifier.raw = true;
+        }
+
+        let is_groupable = matches!(specifier.ty, Type::Display | Type::Custom(_));
+        if current_specifier_index < input.len() || (specifier.grouping.is_some() && !is_groupable)
+        {
             Err(Error::UnexpectedToken)
         } else {
             Ok(specifier)
@@ -176,11 +233,31 @@ impl Specifier {
         self
     }
 
+    /// Builder to specify the digit grouping.
+    pub fn grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = Some(grouping);
+        self
+    }
+
     /// Builder to specify the precision.
     pub fn precision(mut self, precision: Precision) -> Self {
         self.precision = precision;
         self
     }
+
+    /// Builder to format dates and times with the strftime pattern, which sets the type to [`Type::DateTime`].
+    /// The pattern is only validated when parsed.
+    pub fn date_time_format<V: ToString>(mut self, pattern: V) -> Self {
+        self.ty = Type::DateTime;
+        self.date_time_format = Some(pattern.to_string());
+        self
+    }
+
+    /// Builder to set the raw mode, which opts out of escaping.
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
 }
 
 impl Default for Specifier {
@@ -193,15 +270,30 @@ impl Default for Specifier {
             sign: false,
             pad_zero: false,
             width: Width::Fixed(0),
+            grouping: None,
             precision: Precision::Auto,
+            date_time_format: None,
+            raw: false,
+            min_fraction_digits: None,
+            min_integer_digits: None,
         }
     }
 }
 
-impl core::fmt::Display for Specifier {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.fill_character)?;
-        write!(f, "{}", self.alignment)?;
+impl Specifier {
+    /// Writes the shortest spelling which parses back into the same specifier.
+    /// A dynamic precision taken from the positional counter is written as `.*`.
+    pub(crate) fn write_canonical(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        implicit_precision: bool,
+    ) -> core::fmt::Result {
+        if self.alignment != Alignment::Auto {
+            if self.fill_character != ' ' {
+                f.write_char(self.fill_character)?;
+            }
+            write!(f, "{}", self.alignment)?;
+        }
         if self.sign {
             f.write_char('+')?;
         }
@@ -211,17 +303,37 @@ impl core::fmt::Display for Specifier {
         if self.pad_zero {
             f.write_char('0')?;
         }
-        write!(f, "{}", self.width)?;
-        write!(f, "{}", self.precision)?;
-        write!(f, "{}", self.ty)?;
-
+        if !matches!(self.width, Width::Fixed(0)) {
+            write!(f, "{}", self.width)?;
+        }
+        if let Some(grouping) = self.grouping {
+            write!(f, "{}", grouping)?;
+        }
+        match &self.precision {
+            Precision::Dynamic(_) if implicit_precision => f.write_str(".*")?,
+            precision => write!(f, "{}", precision)?,
+        }
+        match &self.date_time_format {
+            Some(pattern) if self.ty == Type::DateTime => f.write_str(pattern)?,
+            _ => write!(f, "{}", self.ty)?,
+        }
+        if self.raw {
+            f.write_str("raw")?;
+        }
         Ok(())
     }
 }
 
-fn parse_fill_character(chars: &[u8]) -> Option<char> {
-    match (chars.first(), chars.get(1)) {
-        (Some(chr), Some(b'<') | Some(b'^') | Some(b'>')) => Some(*chr as char),
+impl core::fmt::Display for Specifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_canonical(f, false)
+    }
+}
+
+fn parse_fill_character(input: &str) -> Option<char> {
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(chr), Some('<') | Some('^') | Some('>')) => Some(chr),
         _ => None,
     }
 }
@@ -251,8 +363,10 @@ fn parse_alternate_form(chars: &[u8]) -> Option<bool> {
 }
 
 fn parse_pad_zero(chars: &[u8]) -> Option<bool> {
-    match chars.first() {
-        Some(b'0') => Some(true),
+    match (chars.first(), chars.get(1)) {
+        // `0$` refers to the first positional argument as width
+        (Some(b'0'), Some(b'$')) => None,
+        (Some(b'0'), _) => Some(true),
         _ => None,
     }
 }
@@ -270,23 +384,69 @@ fn parse_width(chars: &[u8], input: &str) -> Option<(Width, usize)> {
                     until_index += 1;
                 }
                 until_index += 1;
+                if chars.get(until_index) == Some(&b'$') {
+                    return Some((
+                        Width::Dynamic(ArgumentKey::Index(
+                            input[..until_index].parse::<usize>().ok()?,
+                        )),
+                        until_index + 1,
+                    ));
+                }
                 Some((
-                    Width::Fixed(input[..until_index].parse::<u16>().unwrap()),
+                    Width::Fixed(input[..until_index].parse::<u16>().ok()?),
                     until_index,
                 ))
             } else {
-                input.find('$').map(|var_index| {
-                    (
-                        Width::Dynamic(ArgumentKey::Name(input[..var_index].to_string())),
-                        var_index + 1,
-                    )
-                })
+                // The name must not swallow a grouping or precision, e.g. `,.p$`
+                input
+                    .find('$')
+                    .filter(|var_index| {
+                        input[..*var_index]
+                            .chars()
+                            .all(|chr| chr.is_alphanumeric() || chr == '_')
+                    })
+                    .map(|var_index| {
+                        (
+                            Width::Dynamic(ArgumentKey::Name(input[..var_index].to_string())),
+                            var_index + 1,
+                        )
+                    })
             }
         }
         None => None,
     }
 }
 
+/// Parses `,`, `_` or the bracketed spelling `[separator]`, `[separator size]`
+/// and `[separator primary_size,secondary_size]`.
+fn parse_grouping(input: &str) -> Option<(Grouping, usize)> {
+    match input.as_bytes().first() {
+        Some(b',') => return Some((Grouping::new(','), 1)),
+        Some(b'_') => return Some((Grouping::new('_'), 1)),
+        Some(b'[') => {}
+        _ => return None,
+    }
+
+    let separator = input[1..].chars().next()?;
+    let sizes_start = 1 + separator.len_utf8();
+    let sizes_end = sizes_start + input[sizes_start..].find(']')?;
+    let sizes = &input[sizes_start..sizes_end];
+    let (primary_size, secondary_size) = match sizes.split_once(',') {
+        Some((primary_size, secondary_size)) => {
+            (primary_size.parse().ok()?, secondary_size.parse().ok()?)
+        }
+        None if sizes.is_empty() => (3, 3),
+        None => {
+            let size = sizes.parse().ok()?;
+            (size, size)
+        }
+    };
+    Some((
+        Grouping::new(separator).sizes(primary_size, secondary_size),
+        sizes_end + 1,
+    ))
+}
+
 fn parse_precision(
     chars: &[u8],
     input: &str,
@@ -311,8 +471,16 @@ fn parse_precision(
                         until_index += 1;
                     }
                     until_index += 1;
+                    if chars.get(until_index) == Some(&b'$') {
+                        return Some((
+                            Precision::Dynamic(ArgumentKey::Index(
+                                input[1..until_index].parse::<usize>().ok()?,
+                            )),
+                            until_index + 1,
+                        ));
+                    }
                     Some((
-                        Precision::Fixed(input[1..until_index].parse::<u16>().unwrap()),
+                        Precision::Fixed(input[1..until_index].parse::<u16>().ok()?),
                         until_index,
                     ))
                 } else {
@@ -330,16 +498,54 @@ fn parse_precision(
     }
 }
 
-fn parse_ty(chars: &[u8]) -> Option<Type> {
-    match chars.first() {
-        Some(b'?') => Some(Type::Debug),
-        Some(b'b') => Some(Type::Binary),
-        Some(b'o') => Some(Type::Octal),
-        Some(b'e') => Some(Type::LowerExp),
-        Some(b'E') => Some(Type::UpperExp),
-        Some(b'x') => Some(Type::LowerHex),
-        Some(b'X') => Some(Type::UpperHex),
-        Some(b'p') => Some(Type::Pointer),
-        _ => None,
+/// Parses the name of a registered formatter, which may be followed by the `raw` flag.
+fn parse_custom_ty(input: &str, formatters: &FormatterRegistry) -> Option<(&'static str, usize)> {
+    formatters
+        .name(input)
+        .or_else(|| formatters.name(input.strip_suffix("raw")?))
+        .map(|name| (name, name.len()))
+}
+
+/// Parses the strftime pattern of a date and time, which takes the rest of the specifier
+/// except for a trailing `raw` flag, e.g. `%Y raw` or `%Yraw`.
+/// A single `%` is the percent type, also when followed by the `raw` flag.
+#[cfg(feature = "chrono")]
+fn parse_date_time_format(input: &str) -> Result<Option<(&str, usize)>, Error> {
+    use chrono::format::{Item, StrftimeItems};
+
+    let (pattern, len) = match input.strip_suffix("raw") {
+        Some(pattern) => (pattern.trim_end(), pattern.len()),
+        None => (input, input.len()),
+    };
+    if !pattern.starts_with('%') || pattern == "%" {
+        return Ok(None);
+    }
+    if StrftimeItems::new(pattern).any(|item| item == Item::Error) {
+        return Err(Error::InvalidDateTimeFormat(pattern.to_string()));
     }
+    Ok(Some((pattern, len)))
+}
+
+#[cfg(not(feature = "chrono"))]
+fn parse_date_time_format(_input: &str) -> Result<Option<(&str, usize)>, Error> {
+    Ok(None)
+}
+
+fn parse_ty(chars: &[u8]) -> Option<(Type, usize)> {
+    let ty = match chars.first() {
+        Some(b'?') => Type::Debug,
+        Some(b'b') => Type::Binary,
+        Some(b'o') => Type::Octal,
+        Some(b'e') => Type::LowerExp,
+        Some(b'E') => Type::UpperExp,
+        Some(b'x') => Type::LowerHex,
+        Some(b'X') => Type::UpperHex,
+        Some(b'p') => Type::Pointer,
+        Some(b'B') => Type::Bytes,
+        Some(b'i') if chars.get(1) == Some(&b'B') => return Some((Type::BinaryBytes, 2)),
+        Some(b'%') => Type::Percent,
+        Some(b't') => Type::Duration,
+        _ => return None,
+    };
+    Some((ty, 1))
 }
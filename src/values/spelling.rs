@@ -0,0 +1,9 @@
+/// Spelling used when serializing a [`Template`][crate::Template] back into a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Spelling {
+    /// The shortest spelling, which always parses back into an equal template.
+    #[default]
+    Canonical,
+    /// The spelling the template was originally parsed from.
+    Original,
+}
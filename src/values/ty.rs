@@ -1,7 +1,7 @@
 use core::fmt::Write;
 
 /// Type variants of the specifier.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Type {
     Binary,
     Octal,
@@ -13,6 +13,19 @@ pub enum Type {
     Debug,
     Display,
     WidthOrPrecisionAmount,
+    /// Byte size in SI units, e.g. `1.5 MB` for `{:B}`.
+    Bytes,
+    /// Byte size in IEC units, e.g. `1.5 MiB` for `{:iB}`.
+    BinaryBytes,
+    /// Ratio in percent, e.g. `42.1%` for `{:.1%}`.
+    Percent,
+    /// Duration, e.g. `2h 3m` for `{:t}` or `2 hours 3 minutes` for `{:#t}`.
+    Duration,
+    /// Date and time in the strftime pattern of [`Specifier::date_time_format`][crate::Specifier::date_time_format],
+    /// e.g. `{when:%Y-%m-%d}`. The patterns are parsed with the `chrono` feature.
+    DateTime,
+    /// Name of a formatter of the [`FormatterRegistry`][crate::FormatterRegistry].
+    Custom(&'static str),
 }
 
 impl core::fmt::Display for Type {
@@ -26,7 +39,13 @@ impl core::fmt::Display for Type {
             Type::LowerExp => f.write_char('e'),
             Type::UpperExp => f.write_char('E'),
             Type::Debug => f.write_char('?'),
-            Type::Display | Type::WidthOrPrecisionAmount => Ok(()),
+            Type::Bytes => f.write_char('B'),
+            Type::BinaryBytes => f.write_str("iB"),
+            Type::Percent => f.write_char('%'),
+            Type::Duration => f.write_char('t'),
+            // The pattern is written by the specifier
+            Type::Display | Type::WidthOrPrecisionAmount | Type::DateTime => Ok(()),
+            Type::Custom(name) => f.write_str(name),
         }
     }
 }
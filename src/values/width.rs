@@ -3,7 +3,7 @@ use core::fmt::Write;
 use crate::ArgumentKey;
 
 /// Width variants of the specifier.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Width {
     Dynamic(ArgumentKey),
     Fixed(u16),
@@ -0,0 +1,112 @@
+use dfmt::*;
+
+#[test]
+fn bind_named_argument() {
+    let template = Template::parse("Hello {tenant}, {user}!").unwrap();
+    let bound = template
+        .bind("tenant", ArgumentValue::Display(&"acme"))
+        .unwrap();
+
+    assert_eq!(bound, Template::parse("Hello acme, {user}!").unwrap());
+    assert_eq!(
+        bound.requirements().map(|it| it.0).collect::<Vec<_>>(),
+        vec![&ArgumentKey::Name("user".to_string())]
+    );
+    assert_eq!(
+        bound
+            .arguments()
+            .builder()
+            .display("user", &"tom")
+            .format()
+            .unwrap(),
+        "Hello acme, tom!"
+    );
+}
+
+#[test]
+fn bind_keeps_specifier() {
+    let template = Template::parse("{tenant:*^10} {0:#x}").unwrap();
+    let bound = template
+        .bind_all(vec![
+            (&"tenant", ArgumentValue::Display(&"acme")),
+            (&0, ArgumentValue::LowerHex(&255)),
+        ])
+        .unwrap();
+    assert_eq!(bound.to_string(), "***acme*** 0xff");
+}
+
+#[test]
+fn bind_other_type_keeps_placeholder() {
+    let template = Template::parse("{0} {0:x}").unwrap();
+    let bound = template.bind(0, ArgumentValue::Display(&255)).unwrap();
+    assert_eq!(bound.to_string(), "255 {0:x}");
+}
+
+#[test]
+fn bind_implicit_keys_become_explicit() {
+    let template = Template::parse("{} {}").unwrap();
+    let bound = template.bind(0, ArgumentValue::Display(&"a")).unwrap();
+    assert_eq!(bound.to_string(), "a {1}");
+    assert_eq!(
+        bound
+            .arguments()
+            .builder()
+            .display(1, &"b")
+            .format()
+            .unwrap(),
+        "a b"
+    );
+}
+
+#[test]
+fn bind_width() {
+    let template = Template::parse("{name:>w$}|{name:>width$}").unwrap();
+    let bound = template
+        .bind("w", ArgumentValue::WidthOrPrecisionAmount(&5))
+        .unwrap();
+    assert_eq!(bound.to_string(), "{name:>5}|{name:>width$}");
+}
+
+#[test]
+fn bind_waits_for_dynamic_precision() {
+    let template = Template::parse("{value:.p$}").unwrap();
+    let bound = template
+        .bind("value", ArgumentValue::Display(&1.23456))
+        .unwrap();
+    assert_eq!(bound.to_string(), "{value:.p$}");
+
+    let bound = bound
+        .bind("p", ArgumentValue::WidthOrPrecisionAmount(&2))
+        .unwrap();
+    assert_eq!(bound.to_string(), "{value:.2}");
+}
+
+#[test]
+fn bind_escapes_brackets() {
+    let template = Template::parse("{0} {1}").unwrap();
+    let bound = template.bind(0, ArgumentValue::Display(&"{}")).unwrap();
+    assert_eq!(bound.to_string(), "{{}} {1}");
+    assert_eq!(
+        bound
+            .arguments()
+            .builder()
+            .display(1, &"b")
+            .format()
+            .unwrap(),
+        "{} b"
+    );
+}
+
+#[test]
+fn bind_duplicate_argument() {
+    let template = Template::parse("{0}").unwrap();
+    assert_eq!(
+        template
+            .bind_all(vec![
+                (&0, ArgumentValue::Display(&1)),
+                (&0, ArgumentValue::Display(&2)),
+            ])
+            .unwrap_err(),
+        Error::DuplicateArgument(TypedArgumentKey::new(ArgumentKey::Index(0), Type::Display))
+    );
+}
@@ -0,0 +1,125 @@
+use dfmt::*;
+
+fn name(name: &str) -> ArgumentKey {
+    ArgumentKey::Name(name.to_string())
+}
+
+macro_rules! test_compatibility {
+    ($test_name:ident, $source:literal, $translation:literal, [$($incompatibility:expr),*]) => {
+        #[test]
+        fn $test_name() {
+            let source = Template::parse($source).unwrap();
+            let translation = Template::parse($translation).unwrap();
+            let expected: Vec<Incompatibility> = vec![$($incompatibility),*];
+            assert_eq!(source.compatibility(&translation), expected);
+            assert_eq!(source.is_compatible_with(&translation), expected.is_empty());
+        }
+    };
+}
+
+test_compatibility!(same, "{a} and {b:?}", "{b:?} und {a}", []);
+test_compatibility!(reordered_positional, "{} of {}", "{1} von {0}", []);
+test_compatibility!(
+    missing_key,
+    "{a} and {b}",
+    "{a}",
+    [Incompatibility::MissingKey(name("b"))]
+);
+test_compatibility!(
+    unexpected_key,
+    "{a}",
+    "{a} {c}",
+    [Incompatibility::UnexpectedKey(name("c"))]
+);
+test_compatibility!(
+    positional_mismatch,
+    "{} of {}",
+    "{0} von {2}",
+    [
+        Incompatibility::MissingKey(ArgumentKey::Index(1)),
+        Incompatibility::UnexpectedKey(ArgumentKey::Index(2))
+    ]
+);
+test_compatibility!(
+    type_mismatch,
+    "{n:x}",
+    "{n}",
+    [Incompatibility::TypeMismatch {
+        key: name("n"),
+        expected: ArgumentTypeRequirements {
+            lower_hex: true,
+            ..Default::default()
+        },
+        found: ArgumentTypeRequirements {
+            display: true,
+            ..Default::default()
+        }
+    }]
+);
+test_compatibility!(
+    missing_width,
+    "{v:w$} {w}",
+    "{v} {w}",
+    [Incompatibility::MissingWidthOrPrecision(name("w"))]
+);
+test_compatibility!(
+    missing_width_key,
+    "{v:.p$}",
+    "{v}",
+    [Incompatibility::MissingKey(name("p"))]
+);
+test_compatibility!(
+    unexpected_precision,
+    "{v} {p}",
+    "{v:.p$} {p}",
+    [Incompatibility::UnexpectedWidthOrPrecision(name("p"))]
+);
+test_compatibility!(same_dynamic_width, "{v:>w$}", "{v:<w$}", []);
+
+#[test]
+fn display() {
+    let source = Template::parse("{n:x} {w}").unwrap();
+    let translation = Template::parse("{n}{n:?} {v:w$} {w}").unwrap();
+    let messages: Vec<String> = source
+        .compatibility(&translation)
+        .iter()
+        .map(|incompatibility| incompatibility.to_string())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Argument 'n' expected as {:x} but found as {}, {:?}",
+            "Argument 'w' is unexpectedly used as width or precision",
+            "Unexpected argument 'v'"
+        ]
+    );
+}
+
+#[test]
+fn custom_types() {
+    let formatters = FormatterRegistry::new()
+        .formatter("money", |_, _, output| output.write_str("$"))
+        .formatter("bytes", |_, _, output| output.write_str("B"));
+    let parse = |template| Template::parse_with_formatters(template, &formatters).unwrap();
+    let money = parse("{amount:money}");
+
+    assert!(money.is_compatible_with(&parse("{amount:>10money}")));
+    assert_eq!(
+        money.compatibility(&parse("{amount:bytes}")),
+        [Incompatibility::TypeMismatch {
+            key: name("amount"),
+            expected: ArgumentTypeRequirements {
+                custom: vec!["money"],
+                ..Default::default()
+            },
+            found: ArgumentTypeRequirements {
+                custom: vec!["bytes"],
+                ..Default::default()
+            }
+        }]
+    );
+    assert_eq!(
+        money.compatibility(&parse("{amount}"))[0].to_string(),
+        "Argument 'amount' expected as {:money} but found as {}"
+    );
+}
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use dfmt::*;
+
+fn partials() -> BTreeMap<String, Template> {
+    let mut partials = BTreeMap::new();
+    partials.insert(
+        "header".to_string(),
+        Template::parse("Dear {name},\n").unwrap(),
+    );
+    partials.insert(
+        "footer".to_string(),
+        Template::parse("{>signature}").unwrap(),
+    );
+    partials.insert(
+        "signature".to_string(),
+        Template::parse("Regards, {sender:>5}").unwrap(),
+    );
+    partials.insert("loop".to_string(), Template::parse("{>pool}").unwrap());
+    partials.insert("pool".to_string(), Template::parse("{>loop}").unwrap());
+    partials
+}
+
+macro_rules! test_append {
+    ($test_name:ident, $left:literal, $right:literal) => {
+        #[test]
+        fn $test_name() {
+            let left = Template::parse($left).unwrap();
+            let right = Template::parse($right).unwrap();
+            let concatenated = Template::parse(concat!($left, $right)).unwrap();
+
+            assert_eq!(left.clone().append(&right), concatenated);
+            assert_eq!(left + right, concatenated);
+        }
+    };
+}
+
+test_append!(append_literals, "Hello, ", "World!");
+test_append!(append_implicit_keys, "{} {}", "{} {}");
+test_append!(append_explicit_keys, "{} {1}", "{0} {}");
+test_append!(append_implicit_precision, "{:.*}", "{:.*} {}");
+test_append!(append_named_keys, "{a:x} {}", "{a} {b:.p$}");
+#[cfg(feature = "message_format")]
+test_append!(
+    append_implicit_keys_in_choices,
+    "{} ",
+    "{n, plural, one {# {}} other {# {}}}"
+);
+
+#[test]
+fn append_merges_requirements() {
+    let template = Template::parse("{a:x}").unwrap() + Template::parse("{a} {b}").unwrap();
+    assert_eq!(
+        template
+            .requirements()
+            .map(|(key, requirements)| (key.to_string(), requirements.types().count()))
+            .collect::<Vec<_>>(),
+        vec![("a".to_string(), 2), ("b".to_string(), 1)]
+    );
+}
+
+#[test]
+fn append_keeps_original_spelling() {
+    let template = Template::parse("{: <5}").unwrap() + Template::parse("{:-}").unwrap();
+    assert_eq!(template.to_string_with(Spelling::Original), "{: <5}{:-}");
+    assert_eq!(template.to_string(), "{:<5}{:}");
+}
+
+#[test]
+fn append_to_builder_template() {
+    let template = Template::new()
+        .literal("Hello ")
+        .append(&Template::parse("{name}!").unwrap());
+    assert_eq!(template, Template::parse("Hello {name}!").unwrap());
+}
+
+#[test]
+fn parse_partial() {
+    let template = Template::parse("Hi {>footer}").unwrap();
+    assert_eq!(
+        template.segments().nth(1),
+        Some(Segment::Partial {
+            name: "footer",
+            span: Some(3..12)
+        })
+    );
+    assert_eq!(template.to_string(), "Hi {>footer}");
+    assert_eq!(template, Template::new().literal("Hi ").partial("footer"));
+}
+
+#[test]
+fn parse_invalid_partial() {
+    assert_eq!(Template::parse("{>}").unwrap_err(), Error::UnexpectedToken);
+    assert_eq!(
+        Template::parse("{>footer:x}").unwrap_err(),
+        Error::UnexpectedToken
+    );
+}
+
+#[test]
+fn format_with_partials() {
+    let template = Template::parse("{>header}Text\n{>footer}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("name", ArgumentValue::Display(&"Tom"))
+        .unwrap();
+    arguments
+        .add_argument_value("sender", ArgumentValue::Display(&"Ana"))
+        .unwrap();
+
+    assert_eq!(
+        arguments.format_with_partials(&partials()).unwrap(),
+        "Dear Tom,\nText\nRegards,   Ana"
+    );
+    assert_eq!(
+        arguments.format().unwrap_err(),
+        Error::PartialNotFound("header".to_string())
+    );
+}
+
+#[test]
+fn format_with_missing_partial() {
+    let template = Template::parse("{>missing}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .format_with_partials(&partials())
+            .unwrap_err(),
+        Error::PartialNotFound("missing".to_string())
+    );
+}
+
+#[test]
+fn format_with_partial_cycle() {
+    let template = Template::parse("{>loop}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .format_with_partials(&partials())
+            .unwrap_err(),
+        Error::PartialCycle("loop".to_string())
+    );
+}
+
+#[test]
+fn partial_may_be_included_twice() {
+    let template = Template::parse("{>signature} {>signature}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("sender", ArgumentValue::Display(&"Ana"))
+        .unwrap();
+    assert_eq!(
+        arguments.format_with_partials(&partials()).unwrap(),
+        "Regards,   Ana Regards,   Ana"
+    );
+}
+
+#[test]
+fn resolve_partials() {
+    let template = Template::parse("{>header}{>footer}")
+        .unwrap()
+        .resolve_partials(&partials())
+        .unwrap();
+    assert_eq!(
+        template,
+        Template::parse("Dear {name},\nRegards, {sender:>5}").unwrap()
+    );
+    assert_eq!(
+        dformat!(template, name = "Tom", sender = "Ana").unwrap(),
+        "Dear Tom,\nRegards,   Ana"
+    );
+    assert_eq!(
+        Template::parse("{>loop}")
+            .unwrap()
+            .resolve_partials(&partials())
+            .unwrap_err(),
+        Error::PartialCycle("loop".to_string())
+    );
+}
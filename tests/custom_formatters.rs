@@ -0,0 +1,242 @@
+use core::any::Any;
+use core::fmt::Write;
+use dfmt::*;
+
+/// Formats cents as money, padded to the width with the fill character.
+fn money(value: &dyn Any, specifier: &Specifier, output: &mut dyn Write) -> core::fmt::Result {
+    let cents = value.downcast_ref::<i64>().ok_or(core::fmt::Error)?;
+    let precision = match specifier.precision {
+        Precision::Fixed(precision) => precision as usize,
+        _ => 2,
+    };
+    let text = format!("${:.p$}", *cents as f64 / 100.0, p = precision);
+
+    let width = match specifier.width {
+        Width::Fixed(width) => width as usize,
+        Width::Dynamic(_) => unreachable!(),
+    };
+    let padding = width.saturating_sub(text.chars().count());
+    let (before, after) = match specifier.alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+        Alignment::Right | Alignment::Auto => (padding, 0),
+    };
+    for _ in 0..before {
+        output.write_char(specifier.fill_character)?;
+    }
+    output.write_str(&text)?;
+    for _ in 0..after {
+        output.write_char(specifier.fill_character)?;
+    }
+    Ok(())
+}
+
+macro_rules! test_custom {
+    ($test_name:ident, $template:literal, [$(($key:literal, $name:literal, $value:expr)),*], $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let formatters = FormatterRegistry::new()
+                .formatter("money", money)
+                .formatter("bytes", |value, _, output| {
+                    let bytes = value.downcast_ref::<u64>().ok_or(core::fmt::Error)?;
+                    write!(output, "{} B", bytes)
+                });
+            let template = Template::parse_with_formatters($template, &formatters).unwrap();
+            assert_eq!(template.to_string(), $template);
+            assert_eq!(
+                Template::parse_with_formatters(&template.to_string(), &formatters).unwrap(),
+                template
+            );
+            let mut arguments = template.arguments();
+            arguments.set_formatters(&formatters);
+            $(arguments
+                .add_argument_value($key, ArgumentValue::Custom($name, &$value))
+                .unwrap();)*
+            assert_eq!(arguments.format().unwrap(), $expected);
+        }
+    };
+}
+
+test_custom!(
+    plain,
+    "Total: {total:money}",
+    [("total", "money", 1250_i64)],
+    "Total: $12.50"
+);
+test_custom!(
+    width,
+    "[{total:10money}]",
+    [("total", "money", 1250_i64)],
+    "[    $12.50]"
+);
+test_custom!(
+    fill_alignment,
+    "[{total:*<8money}]",
+    [("total", "money", 5_i64)],
+    "[$0.05***]"
+);
+test_custom!(
+    precision,
+    "{total:.0money}",
+    [("total", "money", 1250_i64)],
+    "$12"
+);
+test_custom!(
+    two_types,
+    "{total:money} / {size:bytes}",
+    [("total", "money", 99_i64), ("size", "bytes", 512_u64)],
+    "$0.99 / 512 B"
+);
+test_custom!(
+    starts_like_binary,
+    "{size:>10bytes}",
+    [("size", "bytes", 1_u64)],
+    "1 B"
+);
+test_custom!(
+    raw,
+    "{size:*^10.1bytesraw}",
+    [("size", "bytes", 2_u64)],
+    "2 B"
+);
+
+#[test]
+fn dynamic_width() {
+    let formatters = FormatterRegistry::new().formatter("money", money);
+    let template = Template::parse_with_formatters("[{total:>w$money}]", &formatters).unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .formatters(&formatters)
+            .custom("total", "money", &100_i64)
+            .width_or_precision_amount("w", &7)
+            .format()
+            .unwrap(),
+        "[  $1.00]"
+    );
+}
+
+#[test]
+fn std_types_still_parse() {
+    let formatters = FormatterRegistry::new().formatter("money", money);
+    let template = Template::parse_with_formatters("{a:x}{b:>4?}{c}", &formatters).unwrap();
+    assert_eq!(template, Template::parse("{a:x}{b:>4?}{c}").unwrap());
+}
+
+#[test]
+fn unknown_names() {
+    assert_eq!(
+        Template::parse("{total:money}").unwrap_err(),
+        Error::UnexpectedToken
+    );
+    assert_eq!(
+        Template::parse_with_formatters(
+            "{total:euro}",
+            &FormatterRegistry::new().formatter("money", money)
+        )
+        .unwrap_err(),
+        Error::UnexpectedToken
+    );
+}
+
+#[test]
+fn missing_formatters() {
+    let template = Template::parse_with_formatters(
+        "{total:money}",
+        &FormatterRegistry::new().formatter("money", money),
+    )
+    .unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("total", ArgumentValue::Custom("money", &1_i64))
+        .unwrap();
+    assert_eq!(
+        arguments.format().unwrap_err(),
+        Error::FormatterNotFound("money".to_string())
+    );
+    let other = FormatterRegistry::new();
+    arguments.set_formatters(&other);
+    assert_eq!(
+        arguments.format().unwrap_err(),
+        Error::FormatterNotFound("money".to_string())
+    );
+}
+
+#[test]
+fn values_of_other_types() {
+    let formatters = FormatterRegistry::new().formatter("money", money);
+    let template = Template::parse_with_formatters("{total:money}", &formatters).unwrap();
+    let mut arguments = template.arguments();
+    arguments.set_formatters(&formatters);
+    arguments
+        .add_argument_value("total", ArgumentValue::Display(&1))
+        .unwrap();
+    assert_eq!(
+        arguments.format().unwrap_err(),
+        Error::ArgumentForTypeNotFound(TypedArgumentKey::new(
+            ArgumentKey::Name("total".to_string()),
+            Type::Custom("money")
+        ))
+    );
+
+    arguments.clear();
+    arguments
+        .add_argument_value("total", ArgumentValue::Custom("money", &"12"))
+        .unwrap();
+    assert_eq!(
+        arguments.format().unwrap_err(),
+        Error::Fmt(core::fmt::Error)
+    );
+}
+
+#[test]
+fn duplicate_values() {
+    let template = Template::parse_with_formatters(
+        "{total:money}",
+        &FormatterRegistry::new().formatter("money", money),
+    )
+    .unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("total", ArgumentValue::Custom("money", &1_i64))
+        .unwrap();
+    assert!(matches!(
+        arguments.add_argument_value("total", ArgumentValue::Custom("money", &2_i64)),
+        Err(Error::DuplicateArgument(_))
+    ));
+    arguments
+        .add_argument_value("total", ArgumentValue::Custom("bytes", &2_u64))
+        .unwrap();
+}
+
+#[test]
+fn registry() {
+    let mut formatters = FormatterRegistry::new().formatter("money", money);
+    assert!(formatters.contains("money"));
+    assert!(!formatters.contains("euro"));
+    formatters.insert("euro", |_, _, output| output.write_str("€"));
+    assert_eq!(
+        formatters.names().collect::<Vec<_>>(),
+        vec!["euro", "money"]
+    );
+
+    let mut output = String::new();
+    formatters.get("euro").unwrap()(&(), &Specifier::default(), &mut output).unwrap();
+    assert_eq!(output, "€");
+    assert_eq!(
+        format!("{:?}", formatters),
+        "FormatterRegistry { names: [\"euro\", \"money\"] }"
+    );
+}
+
+#[test]
+fn names_shadowing_specifiers_are_rejected() {
+    for name in ["x", "e", "?", "iB", "xraw", ""] {
+        let inserted = std::panic::catch_unwind(|| FormatterRegistry::new().formatter(name, money));
+        assert!(inserted.is_err(), "{}", name);
+    }
+    assert!(FormatterRegistry::new()
+        .formatter("bytes", money)
+        .contains("bytes"));
+}
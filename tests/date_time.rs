@@ -0,0 +1,169 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use dfmt::*;
+
+macro_rules! test_date_time {
+    ($test_name:ident, $template:literal, $expected:literal) => {
+        test_date_time!(
+            $test_name,
+            $template,
+            NaiveDate::from_ymd_opt(2024, 3, 9)
+                .unwrap()
+                .and_hms_opt(14, 5, 7)
+                .unwrap(),
+            $expected
+        );
+    };
+    ($test_name:ident, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(
+                template
+                    .arguments()
+                    .builder()
+                    .date_time("when", &$value)
+                    .format()
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+test_date_time!(date_and_time, "{when:%Y-%m-%d %H:%M}", "2024-03-09 14:05");
+test_date_time!(
+    date,
+    "{when:%d.%m.%Y}",
+    NaiveDate::from_ymd_opt(2024, 3, 9).unwrap(),
+    "09.03.2024"
+);
+test_date_time!(
+    time,
+    "{when:%H:%M:%S}",
+    NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+    "08:30:00"
+);
+test_date_time!(
+    utc,
+    "{when:%Y-%m-%dT%H:%M:%S%:z}",
+    Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap(),
+    "2024-03-09T14:05:07+00:00"
+);
+test_date_time!(
+    offset,
+    "{when:%H:%M %z}",
+    FixedOffset::east_opt(3600)
+        .unwrap()
+        .with_ymd_and_hms(2024, 3, 9, 15, 5, 7)
+        .unwrap(),
+    "15:05 +0100"
+);
+test_date_time!(names, "{when:%A, %B %e}", "Saturday, March  9");
+test_date_time!(
+    literal_text,
+    "Report of {when:%Y week %V}",
+    "Report of 2024 week 10"
+);
+test_date_time!(percent_literal, "{when:%d%%}", "09%");
+test_date_time!(width, "[{when:>12%Y-%m}]", "[     2024-03]");
+test_date_time!(fill, "[{when:*^11%Y-%m}]", "[**2024-03**]");
+test_date_time!(left, "[{when:<9%Y}]", "[2024     ]");
+
+#[test]
+fn invalid_pattern() {
+    assert_eq!(
+        Template::parse("{when:%Y-%Q}").unwrap_err(),
+        Error::InvalidDateTimeFormat("%Y-%Q".to_string())
+    );
+    assert_eq!(
+        Template::parse("{when:%}").unwrap(),
+        Template::new().specified_argument("when", Specifier::default().ty(Type::Percent))
+    );
+}
+
+#[test]
+fn percent_is_kept() {
+    let template = Template::parse("{:%} {:.1%raw}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .percent(0, &0.5)
+            .percent(1, &0.25)
+            .format()
+            .unwrap(),
+        "50% 25.0%"
+    );
+}
+
+#[test]
+fn missing_time() {
+    let template = Template::parse("{when:%H:%M}").unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .date_time("when", &date)
+            .format()
+            .unwrap_err(),
+        Error::Fmt(core::fmt::Error)
+    );
+}
+
+#[test]
+fn specifier() {
+    let template = Template::parse("{when:>20%Y-%m-%d %H:%M}").unwrap();
+    let Some(Segment::Placeholder {
+        specifier: Some(specifier),
+        ..
+    }) = template.segments().next()
+    else {
+        panic!("{:?}", template);
+    };
+    assert_eq!(
+        specifier,
+        &Specifier::default()
+            .alignment(Alignment::Right)
+            .width(Width::Fixed(20))
+            .date_time_format("%Y-%m-%d %H:%M")
+    );
+    assert_eq!(template.to_string(), "{when:>20%Y-%m-%d %H:%M}");
+}
+
+#[test]
+fn builder() {
+    let template = Template::new()
+        .literal("Generated ")
+        .specified_argument(0, Specifier::default().date_time_format("%Y/%m/%d"));
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .date_time(0, &NaiveDate::from_ymd_opt(2024, 3, 9).unwrap())
+            .format()
+            .unwrap(),
+        "Generated 2024/03/09"
+    );
+}
+
+#[test]
+fn raw() {
+    let template = Template::parse("<td>{when:%d<%m raw}</td> {when:%Yraw}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .escape(Escape::Html)
+            .date_time("when", &NaiveDate::from_ymd_opt(2024, 3, 9).unwrap())
+            .format()
+            .unwrap(),
+        "<td>09<03</td> 2024"
+    );
+    assert_eq!(
+        template.to_string(),
+        "<td>{when:%d<%mraw}</td> {when:%Yraw}"
+    );
+}
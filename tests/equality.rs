@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use dfmt::*;
+
+#[test]
+fn equal_templates_with_different_spelling() {
+    assert_eq!(
+        Template::parse("{: <5} {arg:-x}").unwrap(),
+        Template::parse("{:<5} {arg:x}").unwrap()
+    );
+}
+
+#[test]
+fn different_templates() {
+    assert_ne!(
+        Template::parse("{:<5}").unwrap(),
+        Template::parse("{:>5}").unwrap()
+    );
+    assert_ne!(
+        Template::parse("{}").unwrap(),
+        Template::parse("{0}").unwrap()
+    );
+}
+
+#[test]
+fn builder_and_parsed_template() {
+    assert_eq!(
+        Template::new()
+            .literal("Hello, ")
+            .specified_argument("name", Specifier::default().width(Width::Fixed(10)))
+            .literal("!"),
+        Template::parse("Hello, {name:10}!").unwrap()
+    );
+}
+
+#[test]
+fn split_literals() {
+    let built = Template::new()
+        .literal("a")
+        .literal("")
+        .literal("b{")
+        .argument(0);
+    let parsed = Template::parse("ab{{{0}").unwrap();
+    assert_eq!(built, parsed);
+    assert_eq!(built.fingerprint(), parsed.fingerprint());
+    assert_eq!(HashSet::from([built, parsed]).len(), 1);
+    assert_ne!(
+        Template::new().literal("a").argument(0).literal("b"),
+        Template::parse("ab{0}").unwrap()
+    );
+}
+
+#[test]
+fn dedupe_in_hash_set() {
+    let templates: HashSet<Template> = ["{a} {b}", "{a}  {b}", "{a} {b}", "{a:} {b}"]
+        .into_iter()
+        .map(|it| Template::parse(it).unwrap())
+        .collect();
+    assert_eq!(templates.len(), 3);
+}
+
+#[test]
+fn fingerprint_is_stable() {
+    let template = Template::parse("Hello, {name}!").unwrap();
+    assert_eq!(template.fingerprint(), 0x7f09_2939_67fa_2d5d);
+    assert_eq!(
+        template.fingerprint(),
+        Template::parse("Hello, {name}!").unwrap().fingerprint()
+    );
+    assert_ne!(
+        template.fingerprint(),
+        Template::parse("Hallo, {name}!").unwrap().fingerprint()
+    );
+}
@@ -0,0 +1,120 @@
+use dfmt::*;
+
+macro_rules! test_escape {
+    ($test_name:ident, $escape:expr, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            let mut arguments = template.arguments();
+            arguments.set_escape($escape);
+            arguments
+                .add_argument_value("v", ArgumentValue::Display(&$value))
+                .unwrap();
+            assert_eq!(arguments.format().unwrap(), $expected);
+        }
+    };
+}
+
+test_escape!(none, Escape::None, "<{v}>", "<&>", "<<&>>");
+test_escape!(
+    html,
+    Escape::Html,
+    "<b>{v}</b>",
+    "<a href=\"x\">'&'</a>",
+    "<b>&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;</b>"
+);
+test_escape!(html_padded, Escape::Html, "{v:*^7}", "<>", "**&lt;&gt;***");
+test_escape!(
+    json,
+    Escape::Json,
+    "{{\"name\": \"{v}\"}}",
+    "\"a\\b\"\n\t\u{1}",
+    "{\"name\": \"\\\"a\\\\b\\\"\\n\\t\\u0001\"}"
+);
+test_escape!(
+    json_number,
+    Escape::Json,
+    "{{\"n\": {v:.2}}}",
+    1.5,
+    "{\"n\": 1.50}"
+);
+test_escape!(
+    shell,
+    Escape::Shell,
+    "rm {v}",
+    "it's; rm -rf /",
+    "rm 'it'\\''s; rm -rf /'"
+);
+test_escape!(shell_empty, Escape::Shell, "echo {v}", "", "echo ''");
+test_escape!(csv, Escape::Csv, "{v},x", "a,\"b\"", "\"a,\"\"b\"\"\",x");
+test_escape!(csv_line_break, Escape::Csv, "{v}", "a\nb", "\"a\nb\"");
+test_escape!(csv_plain, Escape::Csv, "{v},x", "a b", "a b,x");
+test_escape!(raw, Escape::Html, "{v:raw}", "<br>", "<br>");
+test_escape!(raw_specified, Escape::Html, "{v:>6raw}", "<br>", "  <br>");
+
+#[test]
+fn literals_are_not_escaped() {
+    let template = Template::parse("<{0}> & {1:?}").unwrap();
+    let mut arguments = template.arguments();
+    arguments.set_escape(Escape::Html);
+    arguments
+        .add_argument_value(0, ArgumentValue::Display(&"<"))
+        .unwrap();
+    arguments
+        .add_argument_value(1, ArgumentValue::Debug(&"&"))
+        .unwrap();
+    assert_eq!(arguments.format().unwrap(), "<&lt;> & &quot;&amp;&quot;");
+}
+
+#[test]
+fn builder() {
+    let template = Template::parse("{name},{age}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .escape(Escape::Csv)
+            .display("name", &"Doe, John")
+            .display("age", &42)
+            .format()
+            .unwrap(),
+        "\"Doe, John\",42"
+    );
+}
+
+#[test]
+fn partials() {
+    let mut partials = std::collections::BTreeMap::new();
+    partials.insert(
+        "name".to_string(),
+        Template::parse("<i>{name}</i>").unwrap(),
+    );
+    let template = Template::parse("<p>{>name}</p>").unwrap();
+    let mut arguments = template.arguments();
+    arguments.set_escape(Escape::Html);
+    arguments
+        .add_argument_value("name", ArgumentValue::Display(&"<script>"))
+        .unwrap();
+    assert_eq!(
+        arguments.format_with_partials(&partials).unwrap(),
+        "<p><i>&lt;script&gt;</i></p>"
+    );
+}
+
+#[test]
+fn raw_round_trip() {
+    let template = Template::parse("{a:raw}{b:>5?raw}").unwrap();
+    assert_eq!(template.to_string(), "{a:raw}{b:>5?raw}");
+    assert_eq!(Template::parse(&template.to_string()).unwrap(), template);
+    assert!(Template::parse("{a:rawx}").is_err());
+}
+
+#[test]
+fn fill_character_in_preceding_output() {
+    let template = Template::parse("ꙮ {0:*>4}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value(0, ArgumentValue::Display(&1))
+        .unwrap();
+    assert_eq!(arguments.format().unwrap(), "ꙮ ***1");
+}
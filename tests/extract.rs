@@ -0,0 +1,111 @@
+use dfmt::*;
+
+macro_rules! test_extract {
+    ($test_name:ident, $template:literal, [$($key:expr => $value:literal),*], $($args:tt)*) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            let formatted = format!($template, $($args)*);
+            let captures = template.extract(&formatted).unwrap();
+            $(assert_eq!(captures.get($key), Some($value));)*
+        }
+    };
+}
+
+test_extract!(
+    log_line,
+    "user={name} id={id:x} took {ms}ms",
+    ["name" => "tom", "id" => "1f", "ms" => "42"],
+    name = "tom",
+    id = 31,
+    ms = 42
+);
+test_extract!(positional, "{} + {} = {}", [0 => "1", 1 => "2", 2 => "3"], 1, 2, 3);
+test_extract!(
+    adjacent_typed,
+    "{0:x}{1:b}",
+    [0 => "ff", 1 => "101"],
+    255,
+    5
+);
+test_extract!(alternate_hex, "{0:#x}|", [0 => "0xff"], 255);
+test_extract!(upper_hex, "{0:X}", [0 => "FF"], 255);
+test_extract!(octal, "{0:#o}", [0 => "0o17"], 15);
+test_extract!(lower_exp, "{0:e} s", [0 => "1.5e3"], 1500.0);
+test_extract!(upper_exp, "{0:E}", [0 => "1.5E-3"], 0.0015);
+test_extract!(padded_right, "[{0:>8}]", [0 => "abc"], "abc");
+test_extract!(padded_left, "[{0:*<8}]", [0 => "abc"], "abc");
+test_extract!(padded_center, "[{0:-^9}]", [0 => "abc"], "abc");
+test_extract!(padded_number, "[{0:5}]", [0 => "42"], 42);
+test_extract!(zero_padded, "[{0:05x}]", [0 => "0002a"], 42);
+test_extract!(signed, "{0:+}", [0 => "+42"], 42);
+test_extract!(non_ascii, "Привет, {}!", [0 => "мир"], "мир");
+test_extract!(repeated_key, "{0}-{0}", [0 => "a-b"], "a-b");
+test_extract!(empty, "[{0}]", [0 => ""], "");
+test_extract!(empty_padded, "[{0:>4}]", [0 => ""], "");
+
+#[test]
+fn no_match() {
+    let template = Template::parse("id={id:x}").unwrap();
+    assert_eq!(template.extract("id=xyz").unwrap_err(), Error::NoMatch);
+    assert_eq!(template.extract("id=").unwrap_err(), Error::NoMatch);
+    assert_eq!(template.extract("user=ff").unwrap_err(), Error::NoMatch);
+    assert!(!template.is_match("id=ff!"));
+    assert!(template.is_match("id=ff"));
+}
+
+#[test]
+fn adjacent_placeholders_without_match() {
+    let template = Template::parse("{a}{b}{c}{d}{e}!").unwrap();
+    assert_eq!(
+        template.extract(&"x".repeat(300)).unwrap_err(),
+        Error::NoMatch
+    );
+}
+
+#[test]
+fn repeated_key_has_to_match() {
+    let template = Template::parse("{0} and {0}").unwrap();
+    assert!(template.is_match("a and a"));
+    assert!(!template.is_match("a and b"));
+}
+
+#[test]
+fn fixed_width_padding() {
+    let template = Template::parse("{0:>4}{1}").unwrap();
+    let captures = template.extract("  ab12").unwrap();
+    assert_eq!(captures.get(0), Some("ab"));
+    assert_eq!(captures.get(1), Some("12"));
+}
+
+#[test]
+fn parse_values() {
+    let template = Template::parse("{a:#b} {b:o} {c:x} {d} {e}").unwrap();
+    let captures = template.extract("0b101 17 -1f -42 2.5").unwrap();
+    assert_eq!(captures.parse_integer::<u8, _>("a").unwrap(), 5);
+    assert_eq!(captures.parse_integer::<u8, _>("b").unwrap(), 15);
+    assert_eq!(captures.parse_integer::<i8, _>("c").unwrap(), -31);
+    assert_eq!(captures.parse_integer::<i64, _>("d").unwrap(), -42);
+    assert_eq!(captures.parse::<f64, _>("e").unwrap(), 2.5);
+    assert_eq!(
+        captures.parse::<f64, _>("missing").unwrap_err(),
+        Error::ArgumentNotFound(ArgumentKey::Name("missing".to_string()))
+    );
+    assert_eq!(
+        captures.parse_integer::<u8, _>("d").unwrap_err(),
+        Error::UnexpectedArgumentValue
+    );
+}
+
+#[test]
+fn iterate_captures() {
+    let template = Template::parse("{b}={a}").unwrap();
+    let captures = template.extract("x=y").unwrap();
+    assert_eq!(
+        captures.iter().collect::<Vec<_>>(),
+        vec![
+            (&ArgumentKey::Name("b".to_string()), "x"),
+            (&ArgumentKey::Name("a".to_string()), "y")
+        ]
+    );
+}
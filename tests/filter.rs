@@ -0,0 +1,235 @@
+use dfmt::*;
+
+macro_rules! test_filter {
+    ($test_name:ident, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(
+                template
+                    .arguments()
+                    .builder()
+                    .display(0, &$value)
+                    .format()
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+macro_rules! test_filter_round_trip {
+    ($test_name:ident, $template:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(template.to_string(), $template);
+        }
+    };
+}
+
+macro_rules! test_filter_error {
+    ($test_name:ident, $template:literal, $error:expr) => {
+        #[test]
+        fn $test_name() {
+            assert_eq!(Template::parse($template).unwrap_err(), $error);
+        }
+    };
+}
+
+test_filter!(upper, "{|upper}", "Hello World", "HELLO WORLD");
+test_filter!(lower, "{|lower}", "Hello World", "hello world");
+test_filter!(title, "{|title}", "hello wORLD", "Hello World");
+test_filter!(title_apostrophe, "{|title}", "it's done", "It's Done");
+test_filter!(trim, "[{|trim}]", "  spaced  ", "[spaced]");
+test_filter!(truncate, "{|truncate(5)}", "Headline", "Headl");
+test_filter!(truncate_short, "{|truncate(10)}", "Headline", "Headline");
+test_filter!(
+    truncate_ellipsis,
+    "{|truncate(6, \"…\")}",
+    "Headline",
+    "Headl…"
+);
+test_filter!(
+    truncate_ellipsis_longer_than_max_len,
+    "{|truncate(2, \"...\")}",
+    "Headline",
+    "..."
+);
+test_filter!(truncate_characters, "{|truncate(2)}", "äöü", "äö");
+test_filter!(chained, "{|lower|trim}", "  MIXED Case ", "mixed case");
+test_filter!(chained_in_order, "{|truncate(4)|upper}", "ab cdef", "AB C");
+test_filter!(named, "{0|upper}", "name", "NAME");
+test_filter!(spaces, "{0| upper | trim }", " name ", "NAME");
+test_filter!(numbers, "{|truncate(3)}", 12345, "123");
+test_filter!(precision, "{|truncate(3):.2}", 1.5, "1.5");
+
+test_filter!(width_left_by_default, "[{|upper:6}]", "abc", "[ABC   ]");
+test_filter!(width_after_filters, "[{|trim:>6}]", "  abc  ", "[   abc]");
+test_filter!(width_fill, "[{|upper:*^7}]", "abc", "[**ABC**]");
+test_filter!(width_numbers, "[{|trim:6}]", 42, "[42    ]");
+test_filter!(width_zero_ignored, "[{|trim:06}]", 42, "[42    ]");
+test_filter!(width_truncated, "[{|truncate(3):>5}]", 123456, "[  123]");
+
+test_filter_round_trip!(round_trip_single, "{name|upper}");
+test_filter_round_trip!(round_trip_chain, "{slug|lower|trim:>20}");
+test_filter_round_trip!(round_trip_truncate, "{title|truncate(30)}");
+test_filter_round_trip!(round_trip_ellipsis, "{title|truncate(30, \"…\")}");
+test_filter_round_trip!(
+    round_trip_escaped_quote,
+    "{title|truncate(30, \"\\\"\\\\\")}"
+);
+test_filter_round_trip!(round_trip_implicit, "{|upper} {|lower}");
+
+test_filter_error!(
+    unknown_filter,
+    "{name|shout}",
+    Error::FilterNotFound("shout".to_string())
+);
+test_filter_error!(
+    empty_filter,
+    "{name|}",
+    Error::FilterNotFound("".to_string())
+);
+test_filter_error!(missing_argument, "{name|truncate}", Error::UnexpectedToken);
+test_filter_error!(
+    unexpected_argument,
+    "{name|upper(3)}",
+    Error::UnexpectedToken
+);
+test_filter_error!(invalid_length, "{name|truncate(x)}", Error::UnexpectedToken);
+test_filter_error!(
+    too_many_arguments,
+    "{name|truncate(3, \"a\", \"b\")}",
+    Error::UnexpectedToken
+);
+test_filter_error!(
+    unquoted_ellipsis,
+    "{name|truncate(3, ...)}",
+    Error::UnexpectedToken
+);
+test_filter_error!(
+    unclosed_quote,
+    "{name|truncate(3, \"…)}",
+    Error::UnexpectedToken
+);
+test_filter_error!(partial, "{>footer|upper}", Error::UnexpectedToken);
+
+#[test]
+fn specifier() {
+    let template = Template::parse("{n|upper:#x}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .lower_hex("n", &255)
+            .format()
+            .unwrap(),
+        "0XFF"
+    );
+}
+
+#[test]
+fn quoted_separators() {
+    let template = Template::parse("{title|truncate(5, \",:}|\")}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .display("title", &"Headline")
+            .format()
+            .unwrap(),
+        "H,:}|"
+    );
+    assert_eq!(template.to_string(), "{title|truncate(5, \",:}|\")}");
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Filter::parse("upper").unwrap(), Filter::Upper);
+    assert_eq!(
+        Filter::parse(" truncate( 30 , \"…\" ) ").unwrap(),
+        Filter::Truncate {
+            max_len: 30,
+            ellipsis: "…".to_string()
+        }
+    );
+    assert_eq!(
+        Filter::parse("truncate(30)").unwrap().to_string(),
+        "truncate(30)"
+    );
+}
+
+#[test]
+fn builder() {
+    let template = Template::new()
+        .literal("Hi ")
+        .argument("name")
+        .filter(Filter::Trim)
+        .filter(Filter::Title)
+        .literal("!");
+    assert_eq!(template.to_string(), "Hi {name|trim|title}!");
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .display("name", &" ada lovelace ")
+            .format()
+            .unwrap(),
+        "Hi Ada Lovelace!"
+    );
+}
+
+#[test]
+fn builder_without_argument() {
+    let template = Template::new().literal("Hi").filter(Filter::Upper);
+    assert_eq!(template.to_string(), "Hi");
+}
+
+#[test]
+fn segments() {
+    let template = Template::parse("{name|upper|trim}").unwrap();
+    let Some(Segment::Placeholder { filters, .. }) = template.segments().next() else {
+        panic!("expected a placeholder");
+    };
+    assert_eq!(filters, &[Filter::Upper, Filter::Trim]);
+}
+
+#[test]
+fn to_regex() {
+    let template = Template::parse("id={id|upper:x}").unwrap();
+    assert_eq!(template.to_regex().unwrap(), "^id=(?P<id>.+?)$");
+}
+
+#[test]
+fn extract() {
+    let template = Template::parse("id={id|upper:x} user={name|upper}").unwrap();
+    let captures = template.extract("id=FF user=TOM").unwrap();
+    assert_eq!(captures.get("id"), Some("FF"));
+    assert_eq!(captures.get("name"), Some("TOM"));
+}
+
+#[cfg(feature = "message_format")]
+#[test]
+fn choice_arms() {
+    let template = Template::parse(
+        "{n, plural, one {{name|upper} has # item} other {{name|lower} has # items}}",
+    )
+    .unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .display("n", &1)
+            .display("name", &"Ana")
+            .format()
+            .unwrap(),
+        "ANA has 1 item"
+    );
+}
+
+#[cfg(feature = "message_format")]
+#[test]
+fn choice_key_with_filter() {
+    assert!(Template::parse("{n|upper, plural, other {#}}").is_err());
+}
@@ -0,0 +1,291 @@
+#![cfg(feature = "fluent")]
+
+use dfmt::*;
+
+macro_rules! test_fluent {
+    ($test_name:ident, $source:literal, $name:literal, [$($key:literal => $value:expr),*], $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let resource = FluentResource::parse($source).unwrap();
+            assert_eq!(
+                resource
+                    .format($name, vec![$((&$key, ArgumentValue::Display(&$value))),*])
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+macro_rules! test_fluent_number {
+    ($test_name:ident, $source:literal, $name:literal, $key:literal => $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let resource = FluentResource::parse($source).unwrap();
+            assert_eq!(
+                resource
+                    .format($name, vec![(&$key, ArgumentValue::Number(&$value))])
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+test_fluent!(text, "hello = Hello, world!", "hello", [], "Hello, world!");
+test_fluent!(
+    variable,
+    "hello = Hello, { $name }!",
+    "hello",
+    ["name" => "Tom"],
+    "Hello, Tom!"
+);
+test_fluent!(
+    compact_placeable,
+    "hello = Hello,{$name}!",
+    "hello",
+    ["name" => "Tom"],
+    "Hello,Tom!"
+);
+test_fluent!(
+    multiline,
+    "about =\n    First line\n      indented\n\n    after blank\n",
+    "about",
+    [],
+    "First line\n  indented\n\nafter blank"
+);
+test_fluent!(
+    inline_and_multiline,
+    "about = First\n    second   \n",
+    "about",
+    [],
+    "First\nsecond"
+);
+test_fluent!(
+    string_literals,
+    r#"braces = { "{" }{ "A" }{ "\"" }{ "}" }"#,
+    "braces",
+    [],
+    "{A\"}"
+);
+test_fluent!(number_literal, "pi = { 3.14 }", "pi", [], "3.14");
+test_fluent!(
+    message_reference,
+    "brand = Nightly\nabout = About { brand }",
+    "about",
+    [],
+    "About Nightly"
+);
+test_fluent!(
+    term_reference,
+    "-brand = Nightly\n    .gender = feminine\nabout = About { -brand } by { $author }",
+    "about",
+    ["author" => "Ana"],
+    "About Nightly by Ana"
+);
+test_fluent!(
+    attribute,
+    "login =\n    .placeholder = Your { $field }\n",
+    "login.placeholder",
+    ["field" => "email"],
+    "Your email"
+);
+test_fluent!(
+    attribute_reference,
+    "login = Login\n    .title = Sign in\nhint = { login.title } here",
+    "hint",
+    [],
+    "Sign in here"
+);
+test_fluent_number!(
+    number_precision,
+    "total = { NUMBER($amount, minimumFractionDigits: 2) } €",
+    "total",
+    "amount" => 12.3456,
+    "12.346 €"
+);
+test_fluent_number!(
+    number_minimum_fraction_digits,
+    "total = { NUMBER($amount, minimumFractionDigits: 2) } €",
+    "total",
+    "amount" => 12.5,
+    "12.50 €"
+);
+test_fluent_number!(
+    number_maximum_fraction_digits,
+    "ratio = { NUMBER($n, maximumFractionDigits: 2) }",
+    "ratio",
+    "n" => 1.5,
+    "1.5"
+);
+test_fluent_number!(
+    number_maximum_fraction_digits_integer,
+    "ratio = { NUMBER($n, maximumFractionDigits: 2) }",
+    "ratio",
+    "n" => 2.0,
+    "2"
+);
+test_fluent_number!(
+    number_fraction_digits_range,
+    "ratio = { NUMBER($n, minimumFractionDigits: 1, maximumFractionDigits: 3) }",
+    "ratio",
+    "n" => 2.0,
+    "2.0"
+);
+test_fluent_number!(
+    number_integer_and_fraction_digits,
+    "ratio = { NUMBER($n, minimumIntegerDigits: 3, maximumFractionDigits: 2) }",
+    "ratio",
+    "n" => 1.5,
+    "001.5"
+);
+test_fluent_number!(
+    number_integer_digits,
+    r#"id = #{ NUMBER($id, minimumIntegerDigits: "4", useGrouping: "false") }"#,
+    "id",
+    "id" => 42,
+    "#0042"
+);
+test_fluent_number!(
+    number_long_integer_digits,
+    "ratio = { NUMBER($n, minimumIntegerDigits: 2, minimumFractionDigits: 1) }",
+    "ratio",
+    "n" => 1234.0,
+    "1234.0"
+);
+test_fluent!(
+    select_string,
+    "liked = { $gender ->\n    [female] She\n    [male] He\n   *[other] They\n} liked it.",
+    "liked",
+    ["gender" => "male"],
+    "He liked it."
+);
+test_fluent!(
+    select_default,
+    "liked = { $gender ->\n    [female] She\n   *[unknown] Someone\n} liked it.",
+    "liked",
+    ["gender" => "robot"],
+    "Someone liked it."
+);
+test_fluent!(
+    select_plural,
+    "files = { $n ->\n    [0] No files\n    [one] { $n } file\n   *[other] { $n } files\n}",
+    "files",
+    ["n" => 1],
+    "1 file"
+);
+test_fluent!(
+    select_exact_number,
+    "files = { $n ->\n    [0] No files\n    [one] { $n } file\n   *[other] { $n } files\n}",
+    "files",
+    ["n" => 0],
+    "No files"
+);
+test_fluent!(
+    select_multiline_variant,
+    "files = { $n ->\n    [one]\n        One\n        file\n   *[other] Many\n}",
+    "files",
+    ["n" => 1],
+    "One\nfile"
+);
+test_fluent!(
+    comments_and_crlf,
+    "### Resource\r\n## Group\r\n# Message\r\nhello = Hi\r\n    there\r\n",
+    "hello",
+    [],
+    "Hi\nthere"
+);
+
+#[test]
+fn locale() {
+    let resource = FluentResource::parse(
+        "files = { $n ->\n    [one] { $n } plik\n    [few] { $n } pliki\n   *[many] { $n } plików\n}",
+    )
+    .unwrap()
+    .locale(Locale::parse("pl").unwrap());
+    let format = |n: &u32| {
+        resource
+            .format("files", vec![(&"n", ArgumentValue::Display(n))])
+            .unwrap()
+    };
+    assert_eq!(format(&1), "1 plik");
+    assert_eq!(format(&3), "3 pliki");
+    assert_eq!(format(&5), "5 plików");
+}
+
+#[test]
+fn names() {
+    let resource =
+        FluentResource::parse("-brand = B\nhello = Hi\n    .title = T\nonly =\n    .attr = A")
+            .unwrap();
+    assert_eq!(
+        resource.names().collect::<Vec<_>>(),
+        ["-brand", "hello", "hello.title", "only.attr"]
+    );
+    assert!(resource.contains("hello.title"));
+    assert!(!resource.contains("only"));
+    assert_eq!(resource.get("hello").unwrap().to_string(), "Hi");
+}
+
+#[test]
+fn missing_message() {
+    let resource = FluentResource::parse("hello = Hi { missing }").unwrap();
+    assert_eq!(
+        resource.format("nope", vec![]),
+        Err(Error::TemplateNotFound("nope".to_string()))
+    );
+    assert_eq!(
+        resource.format("hello", vec![]),
+        Err(Error::InTemplate(
+            "hello".to_string(),
+            Box::new(Error::PartialNotFound("missing".to_string()))
+        ))
+    );
+}
+
+macro_rules! test_invalid {
+    ($test_name:ident, $source:literal, $line:literal) => {
+        #[test]
+        fn $test_name() {
+            assert_eq!(
+                FluentResource::parse($source).unwrap_err(),
+                Error::FluentSyntax($line)
+            );
+        }
+    };
+}
+
+test_invalid!(missing_equals, "hello Hi", 1);
+test_invalid!(empty_message, "ok = fine\nhello =\n", 2);
+test_invalid!(empty_term, "-brand =\n    .gender = x", 2);
+test_invalid!(unclosed_placeable, "hello = { $name", 1);
+test_invalid!(closing_bracket, "hello = a } b", 1);
+test_invalid!(missing_default, "a = { $n ->\n    [one] x\n}", 3);
+test_invalid!(
+    two_defaults,
+    "a = { $n ->\n   *[one] x\n   *[other] y\n}",
+    3
+);
+test_invalid!(unknown_function, "a = { DATETIME($d) }", 1);
+test_invalid!(term_arguments, "a = { -brand(case: \"x\") }", 1);
+test_invalid!(indented_entry, "  hello = Hi", 1);
+test_invalid!(invalid_escape, r#"a = { "\q" }"#, 1);
+test_invalid!(
+    fraction_digits_range,
+    "a = { NUMBER($n, minimumFractionDigits: 3, maximumFractionDigits: 2) }",
+    1
+);
+
+#[test]
+fn nesting_depth() {
+    let nested = |depth: usize| format!("a = {}$n{}", "{ ".repeat(depth), " }".repeat(depth));
+    assert!(FluentResource::parse(&nested(32)).is_ok());
+    assert_eq!(
+        FluentResource::parse(&nested(33)).unwrap_err(),
+        Error::NestingTooDeep(32)
+    );
+    assert_eq!(
+        FluentResource::parse(&nested(100_000)).unwrap_err(),
+        Error::NestingTooDeep(32)
+    );
+}
@@ -0,0 +1,349 @@
+#![cfg(feature = "gettext")]
+
+use dfmt::*;
+
+const PO: &str = r#"
+# Translator comment
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && "
+"n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+#: src/main.rs:1
+msgid "Hello {name}!"
+msgstr "Cześć {name}!"
+
+msgid ""
+"Multi "
+"line"
+msgstr "Wiele\n"
+"linii"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "Otwórz"
+
+#, fuzzy
+msgid "Fuzzy"
+msgstr "Niepewne"
+
+msgid "Untranslated {x}"
+msgstr ""
+
+msgid "{n} file"
+msgid_plural "{n} files"
+msgstr[0] "{n} plik"
+msgstr[1] "{n} pliki"
+msgstr[2] "{n} plików"
+
+#~ msgid "Obsolete"
+#~ msgstr "Przestarzałe"
+"#;
+
+fn catalog() -> Catalog {
+    Catalog::parse_po(PO).unwrap()
+}
+
+#[test]
+fn translates() {
+    assert_eq!(
+        catalog()
+            .tr(
+                "Hello {name}!",
+                vec![(&"name", ArgumentValue::Display(&"Ana"))]
+            )
+            .unwrap(),
+        "Cześć Ana!"
+    );
+    assert_eq!(catalog().tr("Multi line", vec![]).unwrap(), "Wiele\nlinii");
+}
+
+#[test]
+fn falls_back_to_msgid() {
+    let catalog = catalog();
+    assert_eq!(catalog.tr("Fuzzy", vec![]).unwrap(), "Fuzzy");
+    assert_eq!(catalog.tr("Obsolete", vec![]).unwrap(), "Obsolete");
+    assert_eq!(
+        catalog
+            .tr("Untranslated {x}", vec![(&"x", ArgumentValue::Display(&1))])
+            .unwrap(),
+        "Untranslated 1"
+    );
+    assert!(!catalog.contains("Untranslated {x}"));
+}
+
+#[test]
+fn context() {
+    let catalog = catalog();
+    assert_eq!(
+        catalog.tr_context("menu", "Open", vec![]).unwrap(),
+        "Otwórz"
+    );
+    assert_eq!(catalog.tr("Open", vec![]).unwrap(), "Open");
+    assert_eq!(catalog.tr_context("file", "Open", vec![]).unwrap(), "Open");
+}
+
+#[test]
+fn plurals() {
+    let catalog = catalog();
+    assert_eq!(catalog.plural_count(), 3);
+    for (n, expected) in [
+        (1, "1 plik"),
+        (3, "3 pliki"),
+        (5, "5 plików"),
+        (12, "12 plików"),
+        (22, "22 pliki"),
+        (101, "101 plik"),
+    ] {
+        assert_eq!(
+            catalog
+                .tr_plural(
+                    "{n} file",
+                    "{n} files",
+                    n,
+                    vec![(&"n", ArgumentValue::Display(&n))]
+                )
+                .unwrap(),
+            expected
+        );
+    }
+    assert_eq!(
+        catalog
+            .tr_plural(
+                "{n} dog",
+                "{n} dogs",
+                2,
+                vec![(&"n", ArgumentValue::Display(&2))]
+            )
+            .unwrap(),
+        "2 dogs"
+    );
+}
+
+#[test]
+fn default_plural_forms() {
+    let catalog = Catalog::parse_po("msgid \"a\"\nmsgstr \"b\"").unwrap();
+    assert_eq!(catalog.plural_count(), 2);
+    assert_eq!(catalog.plural_index(1), 0);
+    assert_eq!(catalog.plural_index(0), 1);
+    assert_eq!(catalog.len(), 1);
+}
+
+macro_rules! test_plural_forms {
+    ($test_name:ident, $expression:literal, [$(($n:literal, $index:literal)),*]) => {
+        #[test]
+        fn $test_name() {
+            let catalog = Catalog::parse_po(concat!(
+                "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=6; plural=",
+                $expression,
+                ";\\n\""
+            ))
+            .unwrap();
+            $(assert_eq!(catalog.plural_index($n), $index, "n = {}", $n);)*
+        }
+    };
+}
+
+test_plural_forms!(only_one_form, "0", [(0, 0), (1, 0), (7, 0)]);
+test_plural_forms!(french, "(n > 1)", [(0, 0), (1, 0), (2, 1)]);
+test_plural_forms!(
+    arabic,
+    "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)",
+    [(0, 0), (1, 1), (2, 2), (5, 3), (11, 4), (100, 5), (103, 3)]
+);
+test_plural_forms!(
+    not_and_arithmetic,
+    "!(n - 1) + n * 0 + n / 100",
+    [(1, 1), (2, 0), (250, 2)]
+);
+test_plural_forms!(division_by_zero, "n / 0 + n % 0", [(5, 0)]);
+
+#[test]
+fn invalid_plural_forms() {
+    for header in [
+        "nplurals=2; plural=(n != 1",
+        "nplurals=x; plural=n",
+        "plural=n",
+        "nplurals=2; plural=n ? 1",
+    ] {
+        let po = format!("msgid \"\"\nmsgstr \"Plural-Forms: {};\\n\"", header);
+        assert_eq!(
+            Catalog::parse_po(&po).unwrap_err(),
+            Error::InTemplate("Plural-Forms".to_string(), Box::new(Error::UnexpectedToken)),
+            "{}",
+            header
+        );
+    }
+}
+
+#[test]
+fn deeply_nested_plural_forms() {
+    let too_deep = Error::InTemplate(
+        "Plural-Forms".to_string(),
+        Box::new(Error::NestingTooDeep(64)),
+    );
+    for expression in [
+        "(".repeat(100_000) + "n" + &")".repeat(100_000),
+        "!".repeat(100_000) + "n",
+        "n".to_string() + &" + n".repeat(100_000),
+        "n ? 1 : ".repeat(100_000) + "0",
+    ] {
+        let po = format!(
+            "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural={};\\n\"",
+            expression
+        );
+        assert_eq!(Catalog::parse_po(&po).unwrap_err(), too_deep);
+    }
+
+    let po = format!(
+        "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural={};\\n\"",
+        "(".repeat(30) + "n" + &")".repeat(30)
+    );
+    assert!(Catalog::parse_po(&po).is_ok());
+}
+
+#[test]
+fn invalid_po() {
+    for (po, line) in [
+        ("msgid \"a\"\nmsgstr b", 2),
+        ("msgid \"a\"\nmsgstr[1] \"b\"", 2),
+        ("\"a\"", 1),
+        ("msgid \"a\\q\"", 1),
+        ("msgid \"a\"\nmsgtxt \"b\"", 2),
+        ("msgid \"a\"", 1),
+    ] {
+        assert_eq!(
+            Catalog::parse_po(po).unwrap_err(),
+            Error::PoSyntax(line),
+            "{}",
+            po
+        );
+    }
+}
+
+#[test]
+fn invalid_template() {
+    assert_eq!(
+        Catalog::parse_po("msgid \"a {b}\"\nmsgstr \"a {b\"").unwrap_err(),
+        Error::InTemplate("a {b}".to_string(), Box::new(Error::UnexpectedToken))
+    );
+}
+
+/// Writes a `.mo` file of the originals and translations, which are sorted by the original.
+fn mo(entries: &[(&str, &str)], big_endian: bool) -> Vec<u8> {
+    let word = |value: usize| {
+        if big_endian {
+            (value as u32).to_be_bytes()
+        } else {
+            (value as u32).to_le_bytes()
+        }
+    };
+    let originals = 28;
+    let translations = originals + entries.len() * 8;
+    let mut strings_offset = translations + entries.len() * 8;
+
+    let mut bytes = Vec::new();
+    for value in [0x950412de, 0, entries.len(), originals, translations, 0, 0] {
+        bytes.extend(word(value));
+    }
+    let mut strings = Vec::new();
+    for column in [0, 1] {
+        for entry in entries {
+            let string = if column == 0 { entry.0 } else { entry.1 };
+            bytes.extend(word(string.len()));
+            bytes.extend(word(strings_offset));
+            strings.extend(string.as_bytes());
+            strings.push(0);
+            strings_offset += string.len() + 1;
+        }
+    }
+    bytes.extend(strings);
+    bytes
+}
+
+#[test]
+fn mo_catalogs() {
+    let entries = [
+        ("", "Plural-Forms: nplurals=2; plural=(n > 1);\n"),
+        ("Hello {name}!", "Salut {name} !"),
+        ("menu\u{4}Open", "Ouvrir"),
+        ("{n} file\0{n} files", "{n} fichier\0{n} fichiers"),
+    ];
+    for big_endian in [false, true] {
+        let catalog = Catalog::parse_mo(&mo(&entries, big_endian)).unwrap();
+        assert_eq!(
+            catalog
+                .tr(
+                    "Hello {name}!",
+                    vec![(&"name", ArgumentValue::Display(&"Léa"))]
+                )
+                .unwrap(),
+            "Salut Léa !"
+        );
+        assert_eq!(
+            catalog.tr_context("menu", "Open", vec![]).unwrap(),
+            "Ouvrir"
+        );
+        assert_eq!(
+            catalog
+                .tr_plural(
+                    "{n} file",
+                    "{n} files",
+                    0,
+                    vec![(&"n", ArgumentValue::Display(&0))]
+                )
+                .unwrap(),
+            "0 fichier"
+        );
+        assert_eq!(
+            catalog
+                .tr_plural(
+                    "{n} file",
+                    "{n} files",
+                    2,
+                    vec![(&"n", ArgumentValue::Display(&2))]
+                )
+                .unwrap(),
+            "2 fichiers"
+        );
+    }
+}
+
+#[test]
+fn invalid_mo() {
+    assert_eq!(Catalog::parse_mo(b"").unwrap_err(), Error::InvalidMo);
+    assert_eq!(
+        Catalog::parse_mo(b"not a catalog").unwrap_err(),
+        Error::InvalidMo
+    );
+
+    let mut truncated = mo(&[("a", "b")], false);
+    truncated.truncate(40);
+    assert_eq!(Catalog::parse_mo(&truncated).unwrap_err(), Error::InvalidMo);
+}
+
+#[test]
+fn load() {
+    let directory = std::env::temp_dir().join(format!("dfmt_gettext_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let po_path = directory.join("de.po");
+    let mo_path = directory.join("de.mo");
+    std::fs::write(&po_path, "msgid \"Yes\"\nmsgstr \"Ja\"").unwrap();
+    std::fs::write(&mo_path, mo(&[("Yes", "Jawohl")], false)).unwrap();
+
+    assert_eq!(
+        Catalog::load(&po_path).unwrap().tr("Yes", vec![]).unwrap(),
+        "Ja"
+    );
+    assert_eq!(
+        Catalog::load(&mo_path).unwrap().tr("Yes", vec![]).unwrap(),
+        "Jawohl"
+    );
+    assert!(matches!(
+        Catalog::load(directory.join("missing.po")).unwrap_err(),
+        Error::Io { path, kind: std::io::ErrorKind::NotFound, .. }
+            if path == directory.join("missing.po")
+    ));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,156 @@
+use dfmt::*;
+
+macro_rules! test_grouping {
+    ($test_name:ident, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(
+                template
+                    .arguments()
+                    .builder()
+                    .number(0, &$value)
+                    .format()
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+test_grouping!(integer, "{:,}", 1234567, "1,234,567");
+test_grouping!(underscore, "{:_}", 1234567, "1_234_567");
+test_grouping!(short, "{:,}", 123, "123");
+test_grouping!(four_digits, "{:,}", 1234, "1,234");
+test_grouping!(negative, "{:,}", -1234567, "-1,234,567");
+test_grouping!(sign, "{:+,}", 1234, "+1,234");
+test_grouping!(
+    large,
+    "{:,}",
+    u128::MAX,
+    "340,282,366,920,938,463,463,374,607,431,768,211,455"
+);
+test_grouping!(float, "{:,.2}", 1234567.891, "1,234,567.89");
+test_grouping!(float_auto_precision, "{:,}", 1234.5, "1,234.5");
+test_grouping!(small_float, "{:,.3}", -0.5, "-0.500");
+test_grouping!(width, "[{:10,}]", 1234567, "[ 1,234,567]");
+test_grouping!(left, "[{:<10,}]", 1234, "[1,234     ]");
+test_grouping!(center_fill, "[{:*^9,}]", 1234, "[**1,234**]");
+test_grouping!(right_fill, "[{:->8,.1}]", 1234.56, "[-1,234.6]");
+test_grouping!(zero_pad, "{:010,}", 1234, "00,001,234");
+test_grouping!(zero_pad_odd_width, "{:08,}", 1234, "0,001,234");
+test_grouping!(zero_pad_sign, "{:+09,.1}", 1234.5, "+01,234.5");
+test_grouping!(zero_pad_negative, "{:07,}", -1234, "-01,234");
+test_grouping!(infinity, "{:,}", f64::INFINITY, "inf");
+test_grouping!(infinity_zero_pad, "{:+06,}", f64::NEG_INFINITY, "-00inf");
+test_grouping!(not_a_number_width, "[{:<6,}]", f64::NAN, "[NaN   ]");
+test_grouping!(custom_separator, "{:[.]}", 1234567, "1.234.567");
+test_grouping!(custom_sizes, "{:[,3,2]}", 1234567, "12,34,567");
+test_grouping!(custom_size, "{:[ 4]}", 123456789, "1 2345 6789");
+test_grouping!(custom_size_zero, "{:[ 0]}", 1234, "1 2 3 4");
+test_grouping!(custom_width, "[{:12[.4]}]", 123456789, "[ 1.2345.6789]");
+
+#[test]
+fn text_is_kept() {
+    let template = Template::parse("[{:8,}] {:,} {:,}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .display(0, &"text")
+            .display(1, &"20240101 build")
+            .display(2, &"12345")
+            .format()
+            .unwrap(),
+        "[text    ] 20240101 build 12345"
+    );
+}
+
+#[test]
+fn dynamic_width_and_precision() {
+    let template = Template::parse("[{v:w$,.p$}]").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .number("v", &9876543.21)
+            .width_or_precision_amount("w", &14)
+            .width_or_precision_amount("p", &1)
+            .format()
+            .unwrap(),
+        "[   9,876,543.2]"
+    );
+}
+
+#[test]
+fn parse() {
+    let specifier = |template: &str| match Template::parse(template).unwrap().segments().next() {
+        Some(Segment::Placeholder { specifier, .. }) => specifier.cloned(),
+        _ => None,
+    };
+    assert_eq!(
+        specifier("{:>10,.2}"),
+        Some(
+            Specifier::default()
+                .alignment(Alignment::Right)
+                .width(Width::Fixed(10))
+                .grouping(Grouping::new(','))
+                .precision(Precision::Fixed(2))
+        )
+    );
+    assert_eq!(
+        specifier("{:_<5_}"),
+        Some(
+            Specifier::default()
+                .fill_character('_')
+                .alignment(Alignment::Left)
+                .width(Width::Fixed(5))
+                .grouping(Grouping::new('_'))
+        )
+    );
+    assert_eq!(
+        specifier("{:,.p$}"),
+        Some(
+            Specifier::default()
+                .grouping(Grouping::new(','))
+                .precision(Precision::Dynamic(ArgumentKey::Name("p".to_string())))
+        )
+    );
+    assert_eq!(
+        specifier("{:w$_}"),
+        Some(
+            Specifier::default()
+                .width(Width::Dynamic(ArgumentKey::Name("w".to_string())))
+                .grouping(Grouping::new('_'))
+        )
+    );
+
+    for invalid in [
+        "{:,x}", "{:_b}", "{:,e}", "{:,?}", "{:,,}", "{:.2,}", "{:[,}", "{:[,x]}", "{:[,3,]}",
+        "{:[]}",
+    ] {
+        assert_eq!(
+            Template::parse(invalid).unwrap_err(),
+            Error::UnexpectedToken,
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn round_trip() {
+    let template = Template::parse("{:*>+10,.2} {:_}").unwrap();
+    assert_eq!(template.to_string(), "{:*>+10,.2} {:_}");
+
+    for (grouping, spelling) in [
+        (Grouping::indian('\''), "{0:['3,2]}"),
+        (Grouping::new('.'), "{0:[.]}"),
+        (Grouping::new(',').sizes(4, 4), "{0:[,4]}"),
+        (Grouping::new('_').sizes(3, 2), "{0:[_3,2]}"),
+    ] {
+        let built = Template::new().specified_argument(0, Specifier::default().grouping(grouping));
+        assert_eq!(built.to_string(), spelling);
+        assert_eq!(Template::parse(spelling).unwrap(), built);
+    }
+}
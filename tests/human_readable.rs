@@ -0,0 +1,222 @@
+use core::time::Duration;
+use dfmt::*;
+
+macro_rules! test_human_readable {
+    ($test_name:ident, $method:ident, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(template.to_string(), $template);
+            assert_eq!(
+                template
+                    .arguments()
+                    .builder()
+                    .$method(0, &$value)
+                    .format()
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+test_human_readable!(bytes_small, bytes, "{:B}", 512, "512 B");
+test_human_readable!(bytes_zero, bytes, "{:B}", 0_u64, "0 B");
+test_human_readable!(bytes_mega, bytes, "{:B}", 1_536_000_u64, "1.5 MB");
+test_human_readable!(bytes_kilo, bytes, "{:B}", 1_000, "1.0 kB");
+test_human_readable!(bytes_precision, bytes, "{:.2B}", 1_234_567, "1.23 MB");
+test_human_readable!(bytes_no_fraction, bytes, "{:.0B}", 1_536_000, "2 MB");
+test_human_readable!(bytes_rounding, bytes, "{:B}", 999_950, "1.0 MB");
+test_human_readable!(bytes_width, bytes, "{:>8.1B}", 1_536_000, "  1.5 MB");
+test_human_readable!(bytes_auto_alignment, bytes, "{:8B}", 2_000, "  2.0 kB");
+test_human_readable!(bytes_left, bytes, "{:<8B}", 2_000, "2.0 kB  ");
+test_human_readable!(bytes_center, bytes, "{:*^10B}", 2_000, "**2.0 kB**");
+test_human_readable!(bytes_zero_padding, bytes, "{:08B}", 2_000, "002.0 kB");
+test_human_readable!(bytes_negative, bytes, "{:B}", -2_500, "-2.5 kB");
+test_human_readable!(bytes_sign, bytes, "{:+B}", 2_500, "+2.5 kB");
+test_human_readable!(bytes_float, bytes, "{:B}", 1.5e12, "1.5 TB");
+test_human_readable!(bytes_largest_unit, bytes, "{:.0B}", 1e30, "1000000 YB");
+test_human_readable!(bytes_u128, bytes, "{:.0B}", u128::MAX, "340282366920938 YB");
+
+test_human_readable!(binary_bytes_small, binary_bytes, "{:iB}", 1023, "1023 B");
+test_human_readable!(binary_bytes_kibi, binary_bytes, "{:iB}", 1536, "1.5 KiB");
+test_human_readable!(
+    binary_bytes_mebi,
+    binary_bytes,
+    "{:.2iB}",
+    5 * 1024 * 1024,
+    "5.00 MiB"
+);
+test_human_readable!(
+    binary_bytes_width,
+    binary_bytes,
+    "{:>9iB}",
+    1536,
+    "  1.5 KiB"
+);
+
+test_human_readable!(percent, percent, "{:%}", 0.421, "42%");
+test_human_readable!(percent_precision, percent, "{:.1%}", 0.421, "42.1%");
+test_human_readable!(percent_whole, percent, "{:%}", 1, "100%");
+test_human_readable!(percent_negative, percent, "{:.1%}", -0.05, "-5.0%");
+test_human_readable!(percent_sign, percent, "{:+.1%}", 0.05, "+5.0%");
+test_human_readable!(percent_width, percent, "{:>7.1%}", 0.421, "  42.1%");
+test_human_readable!(percent_fill, percent, "{:_<7%}", 0.5, "50%____");
+
+test_human_readable!(duration_zero, duration, "{:t}", Duration::ZERO, "0s");
+test_human_readable!(
+    duration_seconds,
+    duration,
+    "{:t}",
+    Duration::from_secs(42),
+    "42s"
+);
+test_human_readable!(
+    duration_hours,
+    duration,
+    "{:t}",
+    Duration::from_secs(2 * 3600 + 3 * 60 + 4),
+    "2h 3m"
+);
+test_human_readable!(
+    duration_precision,
+    duration,
+    "{:.3t}",
+    Duration::from_secs(2 * 3600 + 3 * 60 + 4),
+    "2h 3m 4s"
+);
+test_human_readable!(
+    duration_one_unit,
+    duration,
+    "{:.1t}",
+    Duration::from_secs(2 * 3600 + 3 * 60),
+    "2h"
+);
+test_human_readable!(
+    duration_zero_units_are_left_out,
+    duration,
+    "{:.3t}",
+    Duration::from_secs(3605),
+    "1h 5s"
+);
+test_human_readable!(
+    duration_cut_off,
+    duration,
+    "{:t}",
+    Duration::from_secs(3605),
+    "1h"
+);
+test_human_readable!(
+    duration_days,
+    duration,
+    "{:t}",
+    Duration::from_secs(3 * 86400 + 7200),
+    "3d 2h"
+);
+test_human_readable!(
+    duration_millis,
+    duration,
+    "{:t}",
+    Duration::from_millis(1500),
+    "1s 500ms"
+);
+test_human_readable!(
+    duration_micros,
+    duration,
+    "{:t}",
+    Duration::from_nanos(1_500),
+    "1µs 500ns"
+);
+test_human_readable!(
+    duration_width,
+    duration,
+    "{:>8t}",
+    Duration::from_secs(90),
+    "  1m 30s"
+);
+test_human_readable!(
+    duration_fill,
+    duration,
+    "{:-<8t}",
+    Duration::from_secs(90),
+    "1m 30s--"
+);
+test_human_readable!(
+    duration_verbose_zero,
+    duration,
+    "{:#t}",
+    Duration::ZERO,
+    "0 seconds"
+);
+test_human_readable!(
+    duration_verbose,
+    duration,
+    "{:#t}",
+    Duration::from_secs(2 * 3600 + 3 * 60),
+    "2 hours 3 minutes"
+);
+test_human_readable!(
+    duration_verbose_singular,
+    duration,
+    "{:#.3t}",
+    Duration::from_secs(3600 + 60 + 1),
+    "1 hour 1 minute 1 second"
+);
+
+#[test]
+fn parse() {
+    for (template, ty) in [
+        ("{:B}", Type::Bytes),
+        ("{:iB}", Type::BinaryBytes),
+        ("{:%}", Type::Percent),
+        ("{:t}", Type::Duration),
+    ] {
+        let template = Template::parse(template).unwrap();
+        let Some(Segment::Placeholder {
+            specifier: Some(specifier),
+            ..
+        }) = template.segments().next()
+        else {
+            panic!("{:?}", template);
+        };
+        assert_eq!(specifier.ty, ty);
+    }
+    assert_eq!(Template::parse("{:i}").unwrap_err(), Error::UnexpectedToken);
+}
+
+#[test]
+fn requirements() {
+    let template = Template::parse("{a:B} {a} {b:iB} {c:%} {d:t}").unwrap();
+    let requirements = template
+        .argument_type_requirements(&"a".to_argument_key())
+        .unwrap();
+    assert!(requirements.bytes && requirements.display);
+    assert!(
+        template
+            .argument_type_requirements(&"b".to_argument_key())
+            .unwrap()
+            .binary_bytes
+    );
+    assert!(
+        template
+            .argument_type_requirements(&"c".to_argument_key())
+            .unwrap()
+            .percent
+    );
+    assert!(
+        template
+            .argument_type_requirements(&"d".to_argument_key())
+            .unwrap()
+            .duration
+    );
+}
+
+#[test]
+fn dformat() {
+    let template = Template::parse("{size:>8.1B} in {elapsed:t} ({ratio:.1%}), {size}").unwrap();
+    let elapsed = Duration::from_secs(125);
+    assert_eq!(
+        dformat!(template, size = 1_536_000, elapsed = elapsed, ratio = 0.421).unwrap(),
+        "  1.5 MB in 2m 5s (42.1%), 1536000"
+    );
+}
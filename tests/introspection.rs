@@ -0,0 +1,106 @@
+use dfmt::*;
+
+#[test]
+fn segments() {
+    let template = Template::parse("Hi {{{name:>w$}}} {}!").unwrap();
+    let segments = template.segments().collect::<Vec<_>>();
+    let name = ArgumentKey::Name("name".to_string());
+    let specifier = Specifier::default()
+        .alignment(Alignment::Right)
+        .width(Width::Dynamic(ArgumentKey::Name("w".to_string())));
+
+    assert_eq!(
+        segments,
+        vec![
+            Segment::Literal {
+                text: "Hi {",
+                span: Some(0..5)
+            },
+            Segment::Placeholder {
+                key: &name,
+                specifier: Some(&specifier),
+                filters: &[],
+                implicit: false,
+                span: Some(5..15)
+            },
+            Segment::Literal {
+                text: "} ",
+                span: Some(15..18)
+            },
+            Segment::Placeholder {
+                key: &ArgumentKey::Index(0),
+                specifier: None,
+                filters: &[],
+                implicit: true,
+                span: Some(18..20)
+            },
+            Segment::Literal {
+                text: "!",
+                span: Some(20..21)
+            },
+        ]
+    );
+}
+
+#[test]
+fn builder_segments_have_no_span() {
+    let template = Template::new().literal("Hi ").argument("name");
+    assert!(template.segments().all(|segment| match segment {
+        Segment::Literal { span, .. }
+        | Segment::Placeholder { span, .. }
+        | Segment::Partial { span, .. }
+        | Segment::Choice { span, .. } => span.is_none(),
+    }));
+}
+
+#[test]
+fn requirements() {
+    let template = Template::parse("{n} {n:x} {name:.p$} {n:?}").unwrap();
+    let requirements = template
+        .requirements()
+        .map(|(key, requirements)| (key.to_string(), requirements.types().collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        requirements,
+        vec![
+            (
+                "n".to_string(),
+                vec![Type::Display, Type::Debug, Type::LowerHex]
+            ),
+            ("name".to_string(), vec![Type::Display]),
+            ("p".to_string(), vec![Type::WidthOrPrecisionAmount]),
+        ]
+    );
+}
+
+#[test]
+fn width_or_precision_keys() {
+    let template = Template::parse("{:w$} {:.p$} {:.*} {w}").unwrap();
+    assert_eq!(
+        template.width_or_precision_keys().collect::<Vec<_>>(),
+        vec![
+            &ArgumentKey::Name("w".to_string()),
+            &ArgumentKey::Name("p".to_string()),
+            &ArgumentKey::Index(2),
+        ]
+    );
+}
+
+#[test]
+fn implicit_argument_count() {
+    assert_eq!(
+        Template::parse("{0} {name}")
+            .unwrap()
+            .implicit_argument_count(),
+        0
+    );
+    assert_eq!(
+        Template::parse("{} {}").unwrap().implicit_argument_count(),
+        2
+    );
+    assert_eq!(
+        Template::parse("{:.*}").unwrap().implicit_argument_count(),
+        2
+    );
+}
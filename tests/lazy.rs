@@ -0,0 +1,118 @@
+use core::cell::Cell;
+use core::fmt::Formatter;
+use dfmt::*;
+
+#[test]
+fn only_used_values_are_computed() {
+    let calls = Cell::new(0);
+    let name = |f: &mut Formatter| {
+        calls.set(calls.get() + 1);
+        f.write_str("Ana")
+    };
+    let payload = |_: &mut Formatter| -> core::fmt::Result { panic!("unused value computed") };
+
+    let template = Template::parse("Hi {name}, bye {name}!").unwrap();
+    let formatted = template
+        .arguments()
+        .builder()
+        .lazy("name", &name)
+        .lazy("payload", &payload)
+        .format()
+        .unwrap();
+    assert_eq!(formatted, "Hi Ana, bye Ana!");
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn not_computed_before_format() {
+    let calls = Cell::new(0);
+    let name = |f: &mut Formatter| {
+        calls.set(calls.get() + 1);
+        f.write_str("Ana")
+    };
+    let template = Template::parse("{name}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("name", ArgumentValue::Lazy(&name))
+        .unwrap();
+    assert_eq!(calls.get(), 0);
+    assert_eq!(arguments.format().unwrap(), "Ana");
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn specifier_is_passed() {
+    let value = |f: &mut Formatter| f.pad("abcdef");
+    let template = Template::parse("[{v:*^8.3}]").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .lazy("v", &value)
+            .format()
+            .unwrap(),
+        "[**abc***]"
+    );
+}
+
+#[test]
+fn computed_once_when_grouped() {
+    let calls = Cell::new(0);
+    let value = |f: &mut Formatter| {
+        calls.set(calls.get() + 1);
+        f.pad("12345")
+    };
+    let template = Template::parse("[{v:>8,}]").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .lazy("v", &value)
+            .format()
+            .unwrap(),
+        "[   12345]"
+    );
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn counts_as_display() {
+    let value = |f: &mut Formatter| f.write_str("x");
+    let template = Template::parse("{v}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("v", ArgumentValue::Lazy(&value))
+        .unwrap();
+    assert_eq!(ArgumentValue::Lazy(&value).to_type(), Type::Display);
+    assert!(matches!(
+        arguments.add_argument_value("v", ArgumentValue::Display(&1)),
+        Err(Error::DuplicateArgument(_))
+    ));
+
+    let template = Template::parse("{v:?}").unwrap();
+    assert!(matches!(
+        template.arguments().builder().lazy("v", &value).format(),
+        Err(Error::ArgumentForTypeNotFound(_))
+    ));
+}
+
+#[test]
+fn errors_are_passed() {
+    let value = |_: &mut Formatter| Err(core::fmt::Error);
+    let template = Template::parse("{v}").unwrap();
+    assert_eq!(
+        template.arguments().lazy_unchecked("v", &value).format(),
+        Err(Error::Fmt(core::fmt::Error))
+    );
+}
+
+#[test]
+fn dyn_fmt() {
+    let value = |f: &mut Formatter| write!(f, "{}", 6 * 7);
+    assert_eq!(
+        "The answer is {}"
+            .format(vec![(&0, ArgumentValue::Lazy(&value))])
+            .unwrap(),
+        "The answer is 42"
+    );
+}
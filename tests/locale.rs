@@ -0,0 +1,187 @@
+#![cfg(feature = "locale")]
+
+use dfmt::*;
+
+macro_rules! test_locale {
+    ($test_name:ident, $locale:literal, $template:literal, $value:expr, $expected:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            assert_eq!(
+                template
+                    .arguments()
+                    .builder()
+                    .locale(Locale::parse($locale).unwrap())
+                    .number(0, &$value)
+                    .format()
+                    .unwrap(),
+                $expected
+            );
+        }
+    };
+}
+
+test_locale!(english, "en", "{:,.2}", 1234.5, "1,234.50");
+test_locale!(german, "de", "{:,.2}", 1234.5, "1.234,50");
+test_locale!(german_without_grouping, "de", "{:.2}", 1234.5, "1234,50");
+test_locale!(german_integer, "de-DE", "{:,}", 1234567, "1.234.567");
+test_locale!(swiss_german, "de-CH", "{:,.1}", 1234.5, "1’234.5");
+test_locale!(austrian_german, "de-AT", "{:,}", 1234, "1\u{a0}234");
+test_locale!(french, "fr-FR", "{:,.1}", -1234.5, "-1\u{202f}234,5");
+test_locale!(russian, "ru", "{:,}", 1234567, "1\u{a0}234\u{a0}567");
+test_locale!(spanish, "es", "{:,.2}", 1234.5, "1.234,50");
+test_locale!(mexican_spanish, "es-MX", "{:,.2}", 1234.5, "1,234.50");
+test_locale!(brazilian_portuguese, "pt-BR", "{:,.2}", 1234.5, "1.234,50");
+test_locale!(indian_english, "en-IN", "{:,}", 1234567, "12,34,567");
+test_locale!(hindi, "hi", "{:,.2}", 12345678.9, "1,23,45,678.90");
+test_locale!(bengali_digits, "bn", "{:,}", 1234567, "১২,৩৪,৫৬৭");
+test_locale!(marathi_digits, "mr", "{}", 1234, "१२३४");
+test_locale!(arabic, "ar-EG", "{:,.1}", 1234.5, "١٬٢٣٤٫٥");
+test_locale!(maghreb_arabic, "ar-MA", "{:,.1}", 1234.5, "1.234,5");
+test_locale!(persian, "fa", "{}", 1234, "۱۲۳۴");
+test_locale!(unknown_language, "xx", "{:,.1}", 1234.5, "1,234.5");
+test_locale!(underscore_is_kept, "de", "{:_.1}", 1234.5, "1_234,5");
+test_locale!(width, "de", "{:>10,.1}", 1234.5, "   1.234,5");
+test_locale!(auto_alignment, "de", "{:10.1}", 1234.5, "    1234,5");
+test_locale!(left_alignment, "fr", "{:*<9,}", 12345, "12\u{202f}345***");
+test_locale!(zero_padding, "de", "{:011,.1}", 1234.5, "0.001.234,5");
+test_locale!(zero_padding_digits, "ar", "{:06}", 42, "٠٠٠٠٤٢");
+test_locale!(sign, "de", "{:+.1}", 2.5, "+2,5");
+test_locale!(infinity_is_kept, "de", "{:.1}", f64::INFINITY, "inf");
+test_locale!(nan_is_kept, "ar", "{}", f64::NAN, "NaN");
+
+#[test]
+fn text_is_kept() {
+    for (locale, template, text, expected) in [
+        ("de", "{}", "1.5", "1.5"),
+        ("ar", "{:,}", "10115", "10115"),
+        ("de", "{:>6}", "v1.2", "  v1.2"),
+        ("ar", "{}", "1.2.3", "1.2.3"),
+        ("de", "{}", "1.", "1."),
+    ] {
+        let template = Template::parse(template).unwrap();
+        assert_eq!(
+            template
+                .arguments()
+                .builder()
+                .locale(Locale::parse(locale).unwrap())
+                .display(0, &text)
+                .format()
+                .unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn debug_is_kept() {
+    let template = Template::parse("{:?}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse("de").unwrap())
+            .debug(0, &1.5)
+            .format()
+            .unwrap(),
+        "1.5"
+    );
+}
+
+#[test]
+fn exponent_is_kept() {
+    let template = Template::parse("{:e}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse("de").unwrap())
+            .lower_exp(0, &1234.5)
+            .format()
+            .unwrap(),
+        "1.2345e3"
+    );
+}
+
+#[test]
+fn hex_digits_are_kept() {
+    let template = Template::parse("{:x}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse("ar").unwrap())
+            .lower_hex(0, &255)
+            .format()
+            .unwrap(),
+        "ff"
+    );
+}
+
+#[test]
+fn explicit_grouping_keeps_its_separator() {
+    let template =
+        Template::new().specified_argument(0, Specifier::default().grouping(Grouping::new('\'')));
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse("de").unwrap())
+            .number(0, &1234567)
+            .format()
+            .unwrap(),
+        "1'234'567"
+    );
+}
+
+#[test]
+fn template_stays_locale_agnostic() {
+    let template = Template::parse("Total: {total:,.2}").unwrap();
+    let total = 9876.543;
+
+    let format = |locale: &str| {
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse(locale).unwrap())
+            .number("total", &total)
+            .format()
+            .unwrap()
+    };
+
+    assert_eq!(format("en-US"), "Total: 9,876.54");
+    assert_eq!(format("nl"), "Total: 9.876,54");
+    assert_eq!(format("pl"), "Total: 9\u{a0}876,54");
+    assert_eq!(template.to_string(), "Total: {total:,.2}");
+}
+
+#[test]
+fn escaping() {
+    let template = Template::parse("{:,}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .locale(Locale::parse("de-CH").unwrap())
+            .escape(Escape::Html)
+            .number(0, &1234)
+            .format()
+            .unwrap(),
+        "1’234"
+    );
+}
+
+#[test]
+fn symbols() {
+    let locale = Locale::parse("hi-IN").unwrap();
+    assert_eq!(locale.decimal_separator(), '.');
+    assert_eq!(locale.grouping(), Grouping::indian(','));
+    assert_eq!(locale.zero_digit(), '0');
+
+    let locale = Locale::parse("ar").unwrap();
+    assert_eq!(locale.decimal_separator(), '٫');
+    assert_eq!(locale.grouping(), Grouping::new('٬'));
+    assert_eq!(locale.zero_digit(), '٠');
+
+    assert_eq!(Locale::default().grouping(), Grouping::new(','));
+}
@@ -0,0 +1,282 @@
+#![cfg(feature = "message_format")]
+
+use dfmt::*;
+
+macro_rules! test_message {
+    ($test_name:ident, $locale:literal, $template:literal, [$(($value:expr, $expected:literal)),*]) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            $(
+                let mut arguments = template.arguments();
+                arguments.set_locale(Locale::parse($locale).unwrap());
+                arguments.add_argument_value("n", ArgumentValue::Number(&$value)).unwrap();
+                assert_eq!(arguments.format().unwrap(), $expected);
+            )*
+        }
+    };
+}
+
+test_message!(
+    english_plural,
+    "en",
+    "{n, plural, one {# file} other {# files}}",
+    [
+        (0, "0 files"),
+        (1, "1 file"),
+        (2, "2 files"),
+        (1.5, "1.5 files")
+    ]
+);
+test_message!(
+    exact_match,
+    "en",
+    "{n, plural, =0 {no files} one {# file} other {# files}}",
+    [(0, "no files"), (0.0, "no files"), (1, "1 file")]
+);
+test_message!(
+    surrounding_text,
+    "en",
+    "Deleted {n, plural, one {one file} other {# files}} from disk.",
+    [
+        (1, "Deleted one file from disk."),
+        (7, "Deleted 7 files from disk.")
+    ]
+);
+test_message!(
+    polish,
+    "pl",
+    "{n, plural, one {# plik} few {# pliki} many {# plików} other {# pliku}}",
+    [
+        (1, "1 plik"),
+        (2, "2 pliki"),
+        (5, "5 plików"),
+        (12, "12 plików"),
+        (22, "22 pliki"),
+        (1.5, "1,5 pliku")
+    ]
+);
+test_message!(
+    russian,
+    "ru-RU",
+    "{n, plural, one {# файл} few {# файла} many {# файлов} other {# файла}}",
+    [
+        (21, "21 файл"),
+        (3, "3 файла"),
+        (11, "11 файлов"),
+        (111, "111 файлов")
+    ]
+);
+test_message!(
+    french,
+    "fr",
+    "{n, plural, one {# fichier} other {# fichiers}}",
+    [(0, "0 fichier"), (1, "1 fichier"), (2, "2 fichiers")]
+);
+test_message!(
+    arabic,
+    "ar",
+    "{n, plural, zero {z} one {o} two {t} few {f} many {m} other {x}}",
+    [
+        (0, "z"),
+        (1, "o"),
+        (2, "t"),
+        (3, "f"),
+        (11, "m"),
+        (100, "x")
+    ]
+);
+test_message!(
+    japanese,
+    "ja",
+    "{n, plural, one {#つ} other {#個}}",
+    [(1, "1個")]
+);
+
+#[test]
+fn nested_choices_and_arguments() {
+    let template = Template::parse(
+        "{gender, select, female {{n, plural, one {She has # item in {place}} other {She has # items in {place}}}} other {{n, plural, one {They have # item} other {They have # items}}}}",
+    )
+    .unwrap();
+    assert_eq!(
+        dformat!(&template, gender = "female", n = 3, place = "the cart").unwrap(),
+        "She has 3 items in the cart"
+    );
+    assert_eq!(
+        dformat!(&template, gender = "other", n = 1, place = "the cart").unwrap(),
+        "They have 1 item"
+    );
+}
+
+#[test]
+fn hash_in_select_refers_to_outer_plural() {
+    let template =
+        Template::parse("{n, plural, other {{kind, select, other {# {kind}s}}}}").unwrap();
+    assert_eq!(dformat!(&template, n = 4, kind = "cat").unwrap(), "4 cats");
+}
+
+#[test]
+fn requirements_include_arms() {
+    let template =
+        Template::parse("{n, plural, one {{a:x}} other {{b:?}}} {c, select, other {#}}").unwrap();
+    let keys: Vec<String> = template
+        .requirements()
+        .map(|(key, _)| key.to_string())
+        .collect();
+    assert_eq!(keys, ["n", "a", "b", "c"]);
+    assert!(
+        template
+            .argument_type_requirements(&ArgumentKey::Name("a".to_string()))
+            .unwrap()
+            .lower_hex
+    );
+}
+
+#[test]
+fn escaped_bracket_in_arm() {
+    let template = Template::parse("{n, plural, other {{{#}}").unwrap();
+    assert_eq!(dformat!(&template, n = 2).unwrap(), "{2");
+}
+
+#[test]
+fn round_trip() {
+    let source = "a {n, plural, =0 {none} one {# {x:>3}} other {{{#}} b";
+    let template = Template::parse(source).unwrap();
+    assert_eq!(
+        template.to_string(),
+        "a {n, plural, =0 {none} one {# {x:>3}} other {{{#}} b"
+    );
+    assert_eq!(Template::parse(&template.to_string()).unwrap(), template);
+    assert_eq!(template.to_string_with(Spelling::Original), source);
+}
+
+#[test]
+fn segments() {
+    let template = Template::parse("x{n, select, other {y}}").unwrap();
+    let segments: Vec<Segment> = template.segments().collect();
+    assert_eq!(
+        segments[1],
+        Segment::Choice {
+            key: &ArgumentKey::Name("n".to_string()),
+            span: Some(1..23)
+        }
+    );
+}
+
+#[test]
+fn partials_in_arms() {
+    let mut partials = std::collections::BTreeMap::new();
+    partials.insert("unit".to_string(), Template::parse("{unit}").unwrap());
+
+    let template = Template::parse("{n, plural, one {# {>unit}} other {# {>unit}s}}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("n", ArgumentValue::Display(&2))
+        .unwrap();
+    arguments
+        .add_argument_value("unit", ArgumentValue::Display(&"byte"))
+        .unwrap();
+    assert_eq!(
+        arguments.format_with_partials(&partials).unwrap(),
+        "2 bytes"
+    );
+
+    let resolved = template.resolve_partials(&partials).unwrap();
+    assert_eq!(dformat!(&resolved, n = 1, unit = "byte").unwrap(), "1 byte");
+}
+
+#[test]
+fn not_a_number() {
+    let template = Template::parse("{n, plural, other {#}}").unwrap();
+    assert_eq!(
+        dformat!(&template, n = "many"),
+        Err(Error::UnexpectedArgumentValue)
+    );
+}
+
+macro_rules! test_invalid {
+    ($test_name:ident, $template:literal) => {
+        #[test]
+        fn $test_name() {
+            assert_eq!(Template::parse($template), Err(Error::UnexpectedToken));
+        }
+    };
+}
+
+test_invalid!(missing_other, "{n, plural, one {x}}");
+test_invalid!(unknown_kind, "{n, choice, other {x}}");
+test_invalid!(unknown_category, "{n, plural, lots {x} other {y}}");
+test_invalid!(invalid_exact, "{n, plural, =x {x} other {y}}");
+test_invalid!(duplicate_selector, "{n, select, a {x} a {y} other {z}}");
+test_invalid!(unclosed_arm, "{n, select, other {x");
+test_invalid!(unclosed_choice, "{n, select, other {x}");
+test_invalid!(missing_key, "{, select, other {x}}");
+test_invalid!(missing_arm, "{n, select, other}");
+
+fn nested_selects(depth: usize) -> String {
+    "{a, select, other {".repeat(depth) + &"}}".repeat(depth)
+}
+
+#[test]
+fn nesting_depth() {
+    assert!(Template::parse(&nested_selects(32)).is_ok());
+    assert_eq!(
+        Template::parse(&nested_selects(33)),
+        Err(Error::NestingTooDeep(32))
+    );
+    assert_eq!(
+        Template::parse(&nested_selects(100_000)),
+        Err(Error::NestingTooDeep(32))
+    );
+    assert_eq!(
+        Template::parse_with(
+            &nested_selects(100_000),
+            &TemplatePolicy::default().max_placeholders(10)
+        ),
+        Err(Error::TooManyPlaceholders(11))
+    );
+}
+
+#[test]
+fn locale() {
+    let locale = Locale::parse("zh_Hant_TW").unwrap();
+    assert_eq!(locale.language(), "zh");
+    assert_eq!(locale.region(), Some("TW"));
+    assert_eq!(locale.to_string(), "zh-TW");
+    assert_eq!(Locale::default().to_string(), "en");
+    assert_eq!(Locale::parse("PT-br").unwrap().to_string(), "pt-BR");
+    assert!(Locale::parse("").is_err());
+    assert!(Locale::parse("english").is_err());
+
+    let portugal = Locale::parse("pt-PT").unwrap();
+    let brazil = Locale::parse("pt-BR").unwrap();
+    assert_eq!(portugal.plural_category("0"), Some(PluralCategory::Other));
+    assert_eq!(brazil.plural_category("0"), Some(PluralCategory::One));
+    assert_eq!(
+        Locale::default().plural_category("1.0"),
+        Some(PluralCategory::Other)
+    );
+    assert_eq!(
+        Locale::default().plural_category("-1"),
+        Some(PluralCategory::One)
+    );
+    assert_eq!(Locale::default().plural_category("1e3"), None);
+}
+
+#[test]
+fn select() {
+    let template =
+        Template::parse("{n, select, female {She} male {He} other {They}} replied.").unwrap();
+    for (value, expected) in [
+        ("female", "She replied."),
+        ("male", "He replied."),
+        ("robot", "They replied."),
+    ] {
+        let mut arguments = template.arguments();
+        arguments
+            .add_argument_value("n", ArgumentValue::Display(&value))
+            .unwrap();
+        assert_eq!(arguments.format().unwrap(), expected);
+    }
+}
@@ -0,0 +1,64 @@
+use dfmt::*;
+
+fn literals(template: &Template) -> Vec<&str> {
+    template
+        .segments()
+        .filter_map(|segment| match segment {
+            Segment::Literal { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn parsed_literals_are_merged() {
+    let template = Template::parse("{{Hello}} {{{0}}}, {{world}}!").unwrap();
+    assert_eq!(literals(&template), vec!["{Hello} {", "}, {world}!"]);
+    assert_eq!(template.segments().count(), 3);
+}
+
+#[test]
+fn argument_free_template_is_single_literal() {
+    let template = Template::parse("Hello {{world}}!").unwrap();
+    assert_eq!(literals(&template), vec!["Hello {world}!"]);
+    assert_eq!(template.arguments().format().unwrap(), "Hello {world}!");
+}
+
+#[test]
+fn builder_literals_are_merged() {
+    let template = Template::new()
+        .literal("Hello")
+        .literal(", ")
+        .argument(0)
+        .literal("!")
+        .literal("!")
+        .optimize();
+    assert_eq!(literals(&template), vec!["Hello, ", "!!"]);
+    assert_eq!(template, Template::parse("Hello, {0}!!").unwrap());
+}
+
+#[test]
+fn optimized_template_keeps_original_spelling() {
+    let template = Template::parse("{{ {:-} }}").unwrap();
+    assert_eq!(template.to_string_with(Spelling::Original), "{{ {:-} }}");
+    assert_eq!(template.to_string(), "{{ {:} }}");
+}
+
+#[test]
+fn optimized_template_formats() {
+    let template = Template::new()
+        .literal("{")
+        .argument(0)
+        .literal("}")
+        .literal("!")
+        .optimize();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .display(0, &42)
+            .format()
+            .unwrap(),
+        "{42}!"
+    );
+}
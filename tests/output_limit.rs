@@ -0,0 +1,210 @@
+use dfmt::*;
+
+/// Writes until the formatter fails, which only terminates if the output is limited.
+struct Endless;
+
+impl core::fmt::Display for Endless {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        loop {
+            f.write_str("ab")?;
+        }
+    }
+}
+
+fn truncate(max_len: usize, ellipsis: &str) -> OutputLimit {
+    OutputLimit::Truncate {
+        max_len,
+        ellipsis: ellipsis.to_string(),
+    }
+}
+
+macro_rules! test_output_limit {
+    ($test_name:ident, $template:literal, $value:expr, $limit:expr, $expected:expr) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            let mut arguments = template.arguments();
+            arguments
+                .add_argument_value("v", ArgumentValue::Display(&$value))
+                .unwrap();
+            arguments.set_output_limit($limit);
+            assert_eq!(arguments.format(), $expected);
+        }
+    };
+}
+
+test_output_limit!(
+    unlimited,
+    "<{v}>",
+    "value",
+    OutputLimit::Unlimited,
+    Ok("<value>".to_string())
+);
+test_output_limit!(
+    exact_fit,
+    "<{v}>",
+    "value",
+    OutputLimit::Error(7),
+    Ok("<value>".to_string())
+);
+test_output_limit!(
+    exceeded,
+    "<{v}>",
+    "value",
+    OutputLimit::Error(6),
+    Err(Error::OutputLimitExceeded)
+);
+test_output_limit!(
+    exceeded_literal,
+    "<{v}> and more",
+    "",
+    OutputLimit::Error(4),
+    Err(Error::OutputLimitExceeded)
+);
+test_output_limit!(
+    endless_error,
+    "{v}",
+    Endless,
+    OutputLimit::Error(1000),
+    Err(Error::OutputLimitExceeded)
+);
+test_output_limit!(
+    endless_truncate,
+    "{v}",
+    Endless,
+    truncate(7, "…"),
+    Ok("abab…".to_string())
+);
+test_output_limit!(
+    truncate_fits,
+    "<{v}>",
+    "value",
+    truncate(7, "..."),
+    Ok("<value>".to_string())
+);
+test_output_limit!(
+    truncate_value,
+    "<{v}>",
+    "value",
+    truncate(6, "..."),
+    Ok("<va...".to_string())
+);
+test_output_limit!(
+    truncate_without_ellipsis,
+    "<{v}>",
+    "value",
+    truncate(3, ""),
+    Ok("<va".to_string())
+);
+test_output_limit!(
+    truncate_long_ellipsis,
+    "<{v}>",
+    "value",
+    truncate(2, "..."),
+    Ok("..".to_string())
+);
+test_output_limit!(
+    truncate_char_boundary,
+    "{v}",
+    "äöü",
+    truncate(5, "."),
+    Ok("äö.".to_string())
+);
+test_output_limit!(
+    padded,
+    "{v:*^9}",
+    "ab",
+    OutputLimit::Error(9),
+    Ok("***ab****".to_string())
+);
+test_output_limit!(
+    padded_truncate,
+    "{v:*^9}",
+    "ab",
+    truncate(6, "."),
+    Ok("***ab.".to_string())
+);
+test_output_limit!(
+    padded_endless,
+    "{v:*>9}",
+    Endless,
+    OutputLimit::Error(20),
+    Err(Error::OutputLimitExceeded)
+);
+test_output_limit!(
+    literal_only,
+    "literal",
+    "",
+    OutputLimit::Error(6),
+    Err(Error::OutputLimitExceeded)
+);
+
+#[test]
+fn escaped() {
+    let template = Template::parse("<p>{v}</p>").unwrap();
+    let mut arguments = template.arguments();
+    arguments.set_escape(Escape::Html);
+    arguments
+        .add_argument_value("v", ArgumentValue::Display(&"<&>"))
+        .unwrap();
+    arguments.set_output_limit(OutputLimit::Error(20));
+    assert_eq!(arguments.format().unwrap(), "<p>&lt;&amp;&gt;</p>");
+    arguments.set_output_limit(OutputLimit::Error(19));
+    assert_eq!(arguments.format().unwrap_err(), Error::OutputLimitExceeded);
+    arguments.set_output_limit(truncate(10, "."));
+    assert_eq!(arguments.format().unwrap(), "<p>&lt;&a.");
+}
+
+#[test]
+fn escaped_endless() {
+    let template = Template::parse("{v}").unwrap();
+    let mut arguments = template.arguments();
+    arguments.set_escape(Escape::Json);
+    arguments
+        .add_argument_value("v", ArgumentValue::Display(&Endless))
+        .unwrap();
+    arguments.set_output_limit(OutputLimit::Error(100));
+    assert_eq!(arguments.format().unwrap_err(), Error::OutputLimitExceeded);
+}
+
+#[test]
+fn partials() {
+    let mut partials = std::collections::BTreeMap::new();
+    partials.insert(
+        "footer".to_string(),
+        Template::parse("Regards, {v}").unwrap(),
+    );
+    let template = Template::parse("Hi!\n{>footer}").unwrap();
+    let mut arguments = template.arguments();
+    arguments
+        .add_argument_value("v", ArgumentValue::Display(&Endless))
+        .unwrap();
+    arguments.set_output_limit(truncate(20, "..."));
+    assert_eq!(
+        arguments.format_with_partials(&partials).unwrap(),
+        "Hi!\nRegards, abab..."
+    );
+}
+
+#[test]
+fn builder() {
+    let template = Template::parse("{a}{b}").unwrap();
+    assert_eq!(
+        template
+            .arguments()
+            .builder()
+            .output_limit(OutputLimit::Error(3))
+            .display("a", &"ab")
+            .display("b", &"cd")
+            .format()
+            .unwrap_err(),
+        Error::OutputLimitExceeded
+    );
+}
+
+#[test]
+fn max_len() {
+    assert_eq!(OutputLimit::Unlimited.max_len(), None);
+    assert_eq!(OutputLimit::Error(5).max_len(), Some(5));
+    assert_eq!(truncate(7, "...").max_len(), Some(7));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "parse_cache")]
+
+use dfmt::*;
+
+// The cache is global, hence everything is tested sequentially within one test.
+#[test]
+fn parse_cache() {
+    ParseCache::set_capacity(2);
+    ParseCache::clear();
+
+    let hello = "Hello, {}!".to_string();
+    let bye = "Bye, {}!".to_string();
+    let welcome = "Welcome, {}!".to_string();
+
+    assert_eq!(dformat!(hello, "World").unwrap(), "Hello, World!");
+    assert_eq!(dformat!(hello, "Tom").unwrap(), "Hello, Tom!");
+    assert_eq!(
+        ParseCache::stats(),
+        ParseCacheStats {
+            hits: 1,
+            misses: 1,
+            len: 1,
+            capacity: 2
+        }
+    );
+
+    // `hello` was used more recently than `bye`, so `bye` is evicted
+    dformat!(bye, "Tom").unwrap();
+    dformat!(hello, "Tom").unwrap();
+    dformat!(welcome, "Tom").unwrap();
+    dformat!(hello, "Tom").unwrap();
+    dformat!(bye, "Tom").unwrap();
+    assert_eq!(
+        ParseCache::stats(),
+        ParseCacheStats {
+            hits: 3,
+            misses: 4,
+            len: 2,
+            capacity: 2
+        }
+    );
+
+    // Errors are not cached
+    assert_eq!(
+        dformat!("Hello {".to_string(), 1).unwrap_err(),
+        Error::UnexpectedToken
+    );
+    assert_eq!(ParseCache::stats().len, 2);
+
+    ParseCache::set_capacity(1);
+    assert_eq!(ParseCache::stats().len, 1);
+
+    ParseCache::set_capacity(0);
+    dformat!(hello, "Tom").unwrap();
+    dformat!(hello, "Tom").unwrap();
+    assert_eq!(ParseCache::stats().len, 0);
+    assert_eq!(ParseCache::capacity(), 0);
+
+    // String templates still parse into plain templates, the cache has its own API
+    let template: Template = "Hi {}".to_template().unwrap();
+    assert_eq!(*ParseCache::get_or_parse("Hi {}").unwrap(), template);
+
+    ParseCache::set_capacity(ParseCache::DEFAULT_CAPACITY);
+    ParseCache::clear();
+    assert_eq!(
+        ParseCache::stats(),
+        ParseCacheStats {
+            hits: 0,
+            misses: 0,
+            len: 0,
+            capacity: ParseCache::DEFAULT_CAPACITY
+        }
+    );
+}
@@ -0,0 +1,170 @@
+use dfmt::*;
+
+fn policy() -> TemplatePolicy {
+    TemplatePolicy::default()
+        .deny(Type::Pointer)
+        .deny(Type::Debug)
+        .max_width(64)
+        .max_precision(16)
+        .max_placeholders(3)
+        .max_length(64)
+        .dynamic_width_or_precision(false)
+}
+
+macro_rules! test_policy {
+    ($test_name:ident, $template:literal, Ok) => {
+        #[test]
+        fn $test_name() {
+            assert_eq!(
+                Template::parse_with($template, &policy()).unwrap(),
+                Template::parse($template).unwrap()
+            );
+        }
+    };
+    ($test_name:ident, $template:literal, $error:expr) => {
+        #[test]
+        fn $test_name() {
+            assert_eq!(
+                Template::parse_with($template, &policy()).unwrap_err(),
+                $error
+            );
+        }
+    };
+}
+
+test_policy!(allowed, "Hello {name:*>10.2}, {:x}{{}}!", Ok);
+test_policy!(max_width, "{:64}", Ok);
+test_policy!(max_precision, "{:.16}", Ok);
+test_policy!(pointer, "{:p}", Error::TypeNotAllowed(Type::Pointer));
+test_policy!(debug, "{:#?}", Error::TypeNotAllowed(Type::Debug));
+test_policy!(width, "{:65535}", Error::WidthTooLarge(65535));
+test_policy!(precision, "{:.17}", Error::PrecisionTooLarge(17));
+test_policy!(
+    dynamic_width,
+    "{:w$}",
+    Error::DynamicWidthOrPrecision(ArgumentKey::Name("w".to_string()))
+);
+test_policy!(
+    dynamic_precision,
+    "{:.*}",
+    Error::DynamicWidthOrPrecision(ArgumentKey::Index(0))
+);
+test_policy!(placeholders, "{} {} {} {}", Error::TooManyPlaceholders(4));
+test_policy!(
+    partials_are_placeholders,
+    "{a}{>b}{>c}{d}",
+    Error::TooManyPlaceholders(4)
+);
+test_policy!(
+    length,
+    "This template is longer than sixty-four bytes, which is the maximum",
+    Error::TemplateTooLong(67)
+);
+test_policy!(invalid_template, "{", Error::UnexpectedToken);
+
+#[test]
+fn length_is_checked_before_parsing() {
+    let template = "{".repeat(65);
+    assert_eq!(
+        Template::parse_with(&template, &policy()).unwrap_err(),
+        Error::TemplateTooLong(65)
+    );
+}
+
+#[test]
+fn placeholders_are_checked_while_parsing() {
+    assert_eq!(
+        Template::parse_with("{} {} {} {} {", &policy()).unwrap_err(),
+        Error::TooManyPlaceholders(4)
+    );
+}
+
+#[test]
+fn formatters() {
+    let formatters = FormatterRegistry::new()
+        .formatter("money", |_, _, output| output.write_str("$"))
+        .formatter("bytes", |_, _, output| output.write_str("B"));
+    let policy = policy().deny(Type::Custom("bytes"));
+    assert!(Template::parse_with_policy_and_formatters("{:money}", &policy, &formatters).is_ok());
+    assert_eq!(
+        Template::parse_with_policy_and_formatters("{:>10bytes}", &policy, &formatters)
+            .unwrap_err(),
+        Error::TypeNotAllowed(Type::Custom("bytes"))
+    );
+    assert_eq!(
+        Template::parse_with_policy_and_formatters("{:money} {} {} {}", &policy, &formatters)
+            .unwrap_err(),
+        Error::TooManyPlaceholders(4)
+    );
+}
+
+#[test]
+fn maximums_forbid_dynamic_values() {
+    let policy = TemplatePolicy::default().max_width(64);
+    assert_eq!(
+        Template::parse_with("{:w$}", &policy).unwrap_err(),
+        Error::DynamicWidthOrPrecision(ArgumentKey::Name("w".to_string()))
+    );
+    assert!(Template::parse_with("{:.*}", &policy).is_ok());
+
+    let policy = TemplatePolicy::default().max_precision(16);
+    assert_eq!(
+        Template::parse_with("{:.*}", &policy).unwrap_err(),
+        Error::DynamicWidthOrPrecision(ArgumentKey::Index(0))
+    );
+    assert!(Template::parse_with("{:w$}", &policy).is_ok());
+}
+
+#[test]
+fn default_allows_everything() {
+    let template = "{:p}{:?}{:65535.65535}{:w$.*}".repeat(100);
+    assert!(Template::parse_with(&template, &TemplatePolicy::default()).is_ok());
+}
+
+#[test]
+fn allow_again() {
+    let policy = policy().allow(Type::Debug);
+    assert!(policy.is_allowed(Type::Debug));
+    assert!(!policy.is_allowed(Type::Pointer));
+    assert!(Template::parse_with("{:?}", &policy).is_ok());
+}
+
+#[test]
+fn deny_display() {
+    let policy = TemplatePolicy::default().deny(Type::Display);
+    assert_eq!(
+        Template::parse_with("{name}", &policy).unwrap_err(),
+        Error::TypeNotAllowed(Type::Display)
+    );
+    assert!(Template::parse_with("{name:x}", &policy).is_ok());
+}
+
+#[test]
+fn check_built_templates() {
+    let template = Template::new()
+        .literal("Hi ")
+        .specified_argument("ptr", Specifier::default().ty(Type::Pointer));
+    assert_eq!(
+        policy().check(&template).unwrap_err(),
+        Error::TypeNotAllowed(Type::Pointer)
+    );
+    assert!(policy().check(&Template::new().argument("name")).is_ok());
+}
+
+#[cfg(feature = "message_format")]
+#[test]
+fn choice_arms() {
+    let policy = policy().max_placeholders(10);
+    assert_eq!(
+        Template::parse_with("{n, plural, one {#} other {{p:p}}}", &policy).unwrap_err(),
+        Error::TypeNotAllowed(Type::Pointer)
+    );
+    assert_eq!(
+        Template::parse_with(
+            "{n, plural, one {# #} other {# # # {a} {b} {c} {d} {e}}}",
+            &policy
+        )
+        .unwrap_err(),
+        Error::TooManyPlaceholders(11)
+    );
+}
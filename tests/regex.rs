@@ -0,0 +1,177 @@
+use dfmt::*;
+use regex::Regex;
+
+macro_rules! test_regex {
+    ($test_name:ident, $template:literal, $pattern:literal, [$($group:literal => $value:literal),*], $($args:tt)*) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            let pattern = template.to_regex().unwrap();
+            assert_eq!(pattern, $pattern);
+
+            let formatted = format!($template, $($args)*);
+            let captures = Regex::new(&pattern).unwrap().captures(&formatted).unwrap();
+            $(assert_eq!(&captures[$group], $value);)*
+        }
+    };
+}
+
+test_regex!(
+    log_line,
+    "user={name} id={id:x} took {ms}ms",
+    r"^user=(?P<name>.+?) id=(?P<id>[0-9a-f]+?) took (?P<ms>.+?)ms$",
+    ["name" => "tom", "id" => "1f", "ms" => "42"],
+    name = "tom",
+    id = 31,
+    ms = 42
+);
+test_regex!(
+    positional,
+    "{} + {} = {}",
+    r"^(?P<_0>.+?) \+ (?P<_1>.+?) = (?P<_2>.+?)$",
+    ["_0" => "1", "_1" => "2", "_2" => "3"],
+    1,
+    2,
+    3
+);
+test_regex!(
+    adjacent_typed,
+    "{0:x}{1:b}",
+    r"^(?P<_0>[0-9a-f]+?)(?P<_1>[01]+?)$",
+    ["_0" => "ff", "_1" => "101"],
+    255,
+    5
+);
+test_regex!(
+    alternate_forms,
+    "{0:#x} {1:#X} {2:#o} {3:#b}",
+    r"^(?P<_0>0x[0-9a-f]+?) (?P<_1>0x[0-9A-F]+?) (?P<_2>0o[0-7]+?) (?P<_3>0b[01]+?)$",
+    ["_0" => "0xff", "_1" => "0xFF", "_2" => "0o17", "_3" => "0b11"],
+    255,
+    255,
+    15,
+    3
+);
+test_regex!(
+    signed_hex,
+    "{0:+x}",
+    r"^(?P<_0>[+-]?[0-9a-f]+?)$",
+    ["_0" => "+ff"],
+    255
+);
+test_regex!(
+    exponents,
+    "{0:e} {1:E}",
+    r"^(?P<_0>-?[0-9]+(?:\.[0-9]+)?e-?[0-9]+?) (?P<_1>-?[0-9]+(?:\.[0-9]+)?E-?[0-9]+?)$",
+    ["_0" => "1.5e3", "_1" => "-1.5E-3"],
+    1500.0,
+    -0.0015
+);
+test_regex!(
+    signed_exponents,
+    "{0:+e} {1:+E}",
+    r"^(?P<_0>[+-]?[0-9]+(?:\.[0-9]+)?e-?[0-9]+?) (?P<_1>[+-]?[0-9]+(?:\.[0-9]+)?E-?[0-9]+?)$",
+    ["_0" => "+4.2e5", "_1" => "-1.5E-3"],
+    420000.0,
+    -0.0015
+);
+test_regex!(
+    escaped_literals,
+    "({0}) [a.b]* {{x}} $^|?",
+    r"^\((?P<_0>.+?)\) \[a\.b\]\* \{x\} \$\^\|\?$",
+    ["_0" => "value"],
+    "value"
+);
+test_regex!(
+    padded_right,
+    "[{0:>8}]",
+    r"^\[ *(?P<_0>.+?)\]$",
+    ["_0" => "abc"],
+    "abc"
+);
+test_regex!(
+    padded_left,
+    "[{0:*<8}]",
+    r"^\[(?P<_0>.+?)\**\]$",
+    ["_0" => "abc"],
+    "abc"
+);
+test_regex!(
+    padded_center,
+    "[{0:-^9}]",
+    r"^\[\-*(?P<_0>.+?)\-*\]$",
+    ["_0" => "abc"],
+    "abc"
+);
+test_regex!(
+    padded_auto,
+    "[{0:5}|{1:5}]",
+    r"^\[ *(?P<_0>.+?) *\| *(?P<_1>.+?) *\]$",
+    ["_0" => "42", "_1" => "ab"],
+    42,
+    "ab"
+);
+test_regex!(
+    repeated_key,
+    "{name}-{name}",
+    r"^(?P<name>.+?)\-(?:.+?)$",
+    ["name" => "a"],
+    name = "a"
+);
+
+#[test]
+fn pointer() {
+    let template = Template::parse("{0:p}").unwrap();
+    let pattern = template.to_regex().unwrap();
+    assert_eq!(pattern, r"^(?P<_0>0x[0-9a-f]+?)$");
+    assert!(Regex::new(&pattern)
+        .unwrap()
+        .is_match(&format!("{:p}", &0u8)));
+}
+
+#[test]
+fn group_names_are_sanitized() {
+    let template = Template::new()
+        .argument(ArgumentKey::Name("first name".to_string()))
+        .literal(" ")
+        .argument(ArgumentKey::Name("2nd".to_string()));
+    assert_eq!(
+        template.to_regex().unwrap(),
+        r"^(?P<first_name>.+?) (?P<_nd>.+?)$"
+    );
+}
+
+#[test]
+fn group_names_are_distinct() {
+    let template = Template::new()
+        .argument(ArgumentKey::Name("a-b".to_string()))
+        .argument(ArgumentKey::Name("a_b".to_string()))
+        .argument(ArgumentKey::Name("a_b_2".to_string()))
+        .literal(" ")
+        .argument(ArgumentKey::Name("_0".to_string()))
+        .argument(ArgumentKey::Index(0));
+    let pattern = template.to_regex().unwrap();
+    assert_eq!(
+        pattern,
+        r"^(?P<a_b>.+?)(?P<a_b_2>.+?)(?P<a_b_2_2>.+?) (?P<_0>.+?)(?P<_0_2>.+?)$"
+    );
+    assert!(Regex::new(&pattern).is_ok());
+}
+
+#[test]
+fn unresolved_partial() {
+    let template = Template::parse("a{>footer}").unwrap();
+    assert_eq!(
+        template.to_regex(),
+        Err(Error::PartialNotFound("footer".to_string()))
+    );
+}
+
+#[test]
+fn rejects_other_text() {
+    let template = Template::parse("id={0:x}").unwrap();
+    let regex = Regex::new(&template.to_regex().unwrap()).unwrap();
+    assert!(regex.is_match("id=ff"));
+    assert!(!regex.is_match("id=fg"));
+    assert!(!regex.is_match("xid=ff"));
+}
@@ -0,0 +1,68 @@
+use dfmt::*;
+
+macro_rules! test_round_trip {
+    ($test_name:ident, $template:literal, $canonical:literal) => {
+        #[test]
+        fn $test_name() {
+            let template = Template::parse($template).unwrap();
+            let canonical = template.to_string();
+            assert_eq!(canonical, $canonical);
+            assert_eq!(Template::parse(&canonical).unwrap(), template);
+            assert_eq!(template.to_string_with(Spelling::Original), $template);
+        }
+    };
+}
+
+test_round_trip!(literal, "Hello, world!", "Hello, world!");
+test_round_trip!(escaped_braces, "{{Hello}}, {{{}}}!", "{{Hello}}, {{{}}}!");
+test_round_trip!(implicit_keys, "{} {} {0}", "{} {} {0}");
+test_round_trip!(explicit_keys, "{0} {1} {0}", "{0} {1} {0}");
+test_round_trip!(named_keys, "{arg} {}", "{arg} {}");
+test_round_trip!(ty_only, "{:x}", "{:x}");
+test_round_trip!(empty_specifier, "{:}", "{:}");
+test_round_trip!(default_fill, "{: <5}", "{:<5}");
+test_round_trip!(custom_fill, "{:*^5}", "{:*^5}");
+test_round_trip!(non_ascii_fill, "{:ꙮ>5}", "{:ꙮ>5}");
+test_round_trip!(minus_sign, "{:-}", "{:}");
+test_round_trip!(everything, "{arg:*>+#020.4o}", "{arg:*>+#020.4o}");
+test_round_trip!(named_width_precision, "{:w$.p$E}", "{:w$.p$E}");
+test_round_trip!(indexed_width_precision, "{:1$.2$}", "{:1$.2$}");
+test_round_trip!(zero_indexed_width, "{1:0$}", "{1:0$}");
+test_round_trip!(implicit_precision, "{:.*} {}", "{:.*} {}");
+test_round_trip!(implicit_precision_explicit_key, "{3:.*} {}", "{3:.*} {}");
+test_round_trip!(zero_pad, "{:05}", "{:05}");
+
+#[test]
+fn builder_literals_are_escaped() {
+    let template = Template::new().literal("{").argument(0).literal("}");
+    assert_eq!(template.to_string(), "{{{0}}}");
+    assert_eq!(
+        Template::parse(&template.to_string())
+            .unwrap()
+            .arguments()
+            .builder()
+            .display(0, &1)
+            .format()
+            .unwrap(),
+        "{1}"
+    );
+}
+
+#[test]
+fn builder_pieces_use_canonical_spelling() {
+    let template = Template::parse("{: <5}").unwrap().specified_argument(
+        0,
+        Specifier::default()
+            .fill_character(' ')
+            .alignment(Alignment::Left),
+    );
+    assert_eq!(template.to_string_with(Spelling::Original), "{: <5}{0:<}");
+}
+
+#[test]
+fn indexed_width_and_precision_format() {
+    assert_eq!(
+        dformat!("{:1$.2$}".to_string(), 1.23456, 10, 2).unwrap(),
+        format!("{:1$.2$}", 1.23456, 10, 2)
+    );
+}
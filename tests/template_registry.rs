@@ -0,0 +1,241 @@
+#![cfg(feature = "std")]
+
+use dfmt::*;
+
+fn registry() -> TemplateRegistry {
+    TemplateRegistry::new()
+        .template("welcome", "Hello {name}!{>footer}")
+        .template("footer", " Regards, {sender}")
+        .template("broken", "Hello {name")
+        .template("includes_broken", "{>broken}")
+        .template("includes_missing", "{>missing}")
+}
+
+#[test]
+fn render() {
+    assert_eq!(
+        registry()
+            .render(
+                "welcome",
+                vec![
+                    (&"name", ArgumentValue::Display(&"Tom")),
+                    (&"sender", ArgumentValue::Display(&"Ana")),
+                ]
+            )
+            .unwrap(),
+        "Hello Tom! Regards, Ana"
+    );
+}
+
+#[test]
+fn get_parses_lazily() {
+    let registry = registry();
+    assert_eq!(
+        registry.get("footer").unwrap(),
+        &Template::parse(" Regards, {sender}").unwrap()
+    );
+    assert_eq!(registry.source("footer"), Some(" Regards, {sender}"));
+    assert!(registry.contains("broken"));
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        vec![
+            "broken",
+            "footer",
+            "includes_broken",
+            "includes_missing",
+            "welcome"
+        ]
+    );
+}
+
+#[test]
+fn errors_contain_template_name() {
+    let registry = registry();
+    assert_eq!(
+        registry.get("broken").unwrap_err(),
+        Error::InTemplate("broken".to_string(), Box::new(Error::UnexpectedToken))
+    );
+    assert_eq!(
+        registry.render("includes_broken", vec![]).unwrap_err(),
+        Error::InTemplate("broken".to_string(), Box::new(Error::UnexpectedToken))
+    );
+    assert_eq!(
+        registry.render("includes_missing", vec![]).unwrap_err(),
+        Error::InTemplate(
+            "includes_missing".to_string(),
+            Box::new(Error::PartialNotFound("missing".to_string()))
+        )
+    );
+    assert_eq!(
+        registry.render("unknown", vec![]).unwrap_err(),
+        Error::TemplateNotFound("unknown".to_string())
+    );
+    assert_eq!(
+        registry
+            .render(
+                "footer",
+                vec![
+                    (&"sender", ArgumentValue::Display(&"Ana")),
+                    (&"sender", ArgumentValue::Display(&"Tom")),
+                ]
+            )
+            .unwrap_err(),
+        Error::InTemplate(
+            "footer".to_string(),
+            Box::new(Error::DuplicateArgument(TypedArgumentKey::new(
+                ArgumentKey::Name("sender".to_string()),
+                Type::Display
+            )))
+        )
+    );
+}
+
+#[test]
+fn insert_replaces_template() {
+    let mut registry = registry();
+    assert_eq!(
+        registry
+            .render("footer", vec![(&"sender", ArgumentValue::Display(&"Ana"))])
+            .unwrap(),
+        " Regards, Ana"
+    );
+    registry.insert("footer", " Bye, {sender}");
+    assert_eq!(
+        registry
+            .render("footer", vec![(&"sender", ArgumentValue::Display(&"Ana"))])
+            .unwrap(),
+        " Bye, Ana"
+    );
+    assert_eq!(
+        registry.remove("footer"),
+        Some(" Bye, {sender}".to_string())
+    );
+    assert!(!registry.contains("footer"));
+}
+
+#[test]
+fn load_dir() {
+    let dir = std::env::temp_dir().join(format!("dfmt_registry_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("welcome.tmpl"), "Hello {name}!{>footer}").unwrap();
+    std::fs::write(dir.join("footer.tmpl"), " Bye").unwrap();
+    std::fs::write(dir.join("ignored.txt"), "{").unwrap();
+
+    let registry = TemplateRegistry::load_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        vec!["footer", "welcome"]
+    );
+    assert_eq!(
+        registry
+            .render("welcome", vec![(&"name", ArgumentValue::Display(&"Tom"))])
+            .unwrap(),
+        "Hello Tom! Bye"
+    );
+}
+
+struct TemplateDir(std::path::PathBuf);
+
+impl TemplateDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("dfmt_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    /// Writes the file and moves its modification time forward,
+    /// so that the change is detected independent of the timestamp resolution.
+    fn write(&self, name: &str, content: &str, age: u64) {
+        let path = self.0.join(name);
+        std::fs::write(&path, content).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(age))
+            .unwrap();
+    }
+}
+
+impl Drop for TemplateDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn refresh() {
+    let dir = TemplateDir::new("refresh");
+    dir.write("alert.tmpl", "Disk {disk} is full", 1);
+    let mut registry = TemplateRegistry::load_dir(&dir.0).unwrap();
+    let render = |registry: &TemplateRegistry| {
+        registry
+            .render("alert", vec![(&"disk", ArgumentValue::Display(&"sda"))])
+            .unwrap()
+    };
+    assert_eq!(render(&registry), "Disk sda is full");
+
+    dir.write("alert.tmpl", "Disk {disk} is at 100%", 2);
+    assert_eq!(render(&registry), "Disk sda is full");
+    assert!(registry.refresh().is_empty());
+    assert_eq!(render(&registry), "Disk sda is at 100%");
+
+    // Changes within the resolution of the modification time are told apart by the length
+    dir.write("alert.tmpl", "Disk {disk} is at 99%", 2);
+    assert!(registry.refresh().is_empty());
+    assert_eq!(render(&registry), "Disk sda is at 99%");
+}
+
+#[test]
+fn refresh_keeps_last_good_template() {
+    let dir = TemplateDir::new("refresh_error");
+    dir.write("alert.tmpl", "Disk {disk} is full", 1);
+    let mut registry = TemplateRegistry::load_dir(&dir.0).unwrap();
+    registry.get("alert").unwrap();
+
+    dir.write("alert.tmpl", "Disk {disk is full", 2);
+    let error = Error::InTemplate("alert".to_string(), Box::new(Error::UnexpectedToken));
+    assert_eq!(registry.refresh(), vec![error.clone()]);
+    assert_eq!(registry.error("alert"), Some(&error));
+    assert_eq!(registry.refresh(), vec![error.clone()]);
+    assert_eq!(registry.error("alert"), Some(&error));
+    assert_eq!(registry.source("alert"), Some("Disk {disk} is full"));
+    assert_eq!(
+        registry
+            .render("alert", vec![(&"disk", ArgumentValue::Display(&"sda"))])
+            .unwrap(),
+        "Disk sda is full"
+    );
+
+    dir.write("alert.tmpl", "Disk {disk} is at 100%", 3);
+    assert!(registry.refresh().is_empty());
+    assert_eq!(registry.error("alert"), None);
+    assert_eq!(
+        registry.get("alert").unwrap(),
+        &Template::parse("Disk {disk} is at 100%").unwrap()
+    );
+}
+
+#[test]
+fn refresh_reports_missing_file() {
+    let dir = TemplateDir::new("refresh_missing");
+    dir.write("alert.tmpl", "Disk {disk} is full", 1);
+    let mut registry = TemplateRegistry::load_dir(&dir.0).unwrap();
+    registry.get("alert").unwrap();
+
+    std::fs::remove_file(dir.0.join("alert.tmpl")).unwrap();
+    let errors = registry.refresh();
+    let [Error::InTemplate(name, error)] = errors.as_slice() else {
+        panic!("unexpected errors {:?}", errors);
+    };
+    assert_eq!(name, "alert");
+    assert!(matches!(
+        error.as_ref(),
+        Error::Io { path, kind: std::io::ErrorKind::NotFound, message }
+            if path == &dir.0.join("alert.tmpl") && !message.is_empty()
+    ));
+    assert!(error.to_string().contains("alert.tmpl"));
+    assert!(registry.get("alert").is_ok());
+}